@@ -65,7 +65,7 @@ pub fn benchmark_a_experiment(
     env.spawn_local_promise(async move {
         while let Ok(v) = rx.recv().await {
             // println!("{}", v);
-            callback.call_without_args(None)?;
+            callback.borrow(&env)?.call_without_args(None)?;
         }
         env.get_undefined()
     })
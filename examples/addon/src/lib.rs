@@ -5,6 +5,8 @@ use std::time::Duration;
 
 use async_std::channel;
 use async_std::task;
+use futures::AsyncWriteExt;
+use futures::StreamExt;
 use napi::*;
 use napi_ext::*;
 use napi_derive::napi;
@@ -16,7 +18,7 @@ pub fn example_a(
 ) -> napi::Result<()> {
   env.spawn_local(async move {
     task::sleep(Duration::from_millis(1000)).await;
-    callback.call_without_args(None)?;
+    callback.borrow(&env)?.call_without_args(None)?;
     Ok(())
   })
 }
@@ -24,7 +26,7 @@ pub fn example_a(
 #[napi]
 pub fn example_b(
   env: Env,
-  callback: JsRc<JsFunction>,
+  #[napi(ts_arg_type = "(value: number) => void")] callback: JsCallback<(i32,)>,
 ) -> napi::Result<()> {
   let (tx, rx) = channel::unbounded();
 
@@ -38,7 +40,10 @@ pub fn example_b(
   env.spawn_local(async move {
     while let Ok(value) = rx.recv().await {
       println!("RS: {}", value);
-      callback.call(None, &[env.create_int32(value)?])?;
+      env.with_scope(|_env| {
+        callback.call((value,))?;
+        Ok(())
+      })?;
     }
 
     Ok(())
@@ -63,16 +68,342 @@ pub fn example_d(env: Env, value: JsRc<JsString>) -> napi::Result<JsObject> {
   })
 }
 
+#[napi]
+pub fn example_e(env: Env) -> napi::Result<JsObject> {
+  let (tx, rx) = channel::bounded(1);
 
+  thread::spawn(move || {
+    for i in 0..10 {
+      if tx.send_blocking(Ok(i)).is_err() {
+        break;
+      }
+      thread::sleep(Duration::from_millis(500));
+    }
+  });
+
+  stream::to_async_iterator(&env, rx)
+}
 
+#[napi]
+pub fn example_f(
+  env: Env,
+  iterable: JsRc<JsObject>,
+  callback: JsRc<JsFunction>,
+) -> napi::Result<JsObject> {
+  env.spawn_local_promise(async move {
+    let mut values = stream::JsAsyncIterator::from(iterable).take(3);
+    while let Some(value) = values.next().await {
+      callback.borrow(&env)?.call(None, &[value?])?;
+    }
+    env.get_undefined()
+  })
+}
+
+#[napi]
+pub fn example_g(
+  env: Env,
+  readable: JsRc<JsObject>,
+  writable: JsRc<JsObject>,
+) -> napi::Result<JsObject> {
+  env.spawn_local_promise(async move {
+    let reader = stream::JsReadable::new(readable)?;
+    let mut writer = stream::JsWritable::new(writable)?;
+    let copied = futures::io::copy(reader, &mut writer).await?;
+    writer.close().await?;
+    Ok(copied as u32)
+  })
+}
+
+#[napi]
+pub fn example_h(env: Env) -> napi::Result<JsObject> {
+  let text = (0..10).map(|i| format!("Line {}\n", i)).collect::<String>();
+  stream::to_readable(&env, futures::io::Cursor::new(text.into_bytes()))
+}
+
+#[napi]
+pub fn example_i(env: Env) -> napi::Result<JsObject> {
+  let lines = futures::stream::iter(0..10).then(|i| async move {
+    task::sleep(Duration::from_millis(100)).await;
+    Ok(bindgen_prelude::Buffer::from(format!("Line {}\n", i).into_bytes()))
+  });
+  stream::to_readable_stream(&env, lines)
+}
+
+#[napi]
+pub fn example_j(
+  env: Env,
+  #[napi(ts_arg_type = "(value: string) => void")] callback: JsCallback<(String,)>,
+) -> napi::Result<JsObject> {
+  let (tx, rx) = channel::bounded::<String>(1);
+
+  env.spawn_local(async move {
+    while let Ok(value) = rx.recv().await {
+      callback.call((value.to_uppercase(),))?;
+    }
+    Ok(())
+  })?;
+
+  let sink = futures::sink::unfold(tx, |tx, value: String| async move {
+    tx.send(value)
+      .await
+      .map_err(|e| napi::Error::from_reason(e.to_string()))?;
+    Ok(tx)
+  });
+  stream::to_writable_stream(&env, sink)
+}
+
+#[napi]
+pub fn example_k(
+  env: Env,
+  emitter: JsRc<JsObject>,
+) -> napi::Result<()> {
+  let mut messages = stream::EventStream::new(emitter.clone(), "message")?.take(3);
+  let replies = stream::LocalEmitter::new(&env, emitter)?;
+
+  env.spawn_local(async move {
+    while let Some(args) = messages.next().await {
+      let message = args[0].get()?.coerce_to_string()?.into_utf8()?.into_owned()?;
+      let replies = replies.clone();
+      thread::spawn(move || replies.emit("reply", message.to_uppercase()));
+    }
+    Ok(())
+  })
+}
+
+#[napi]
+pub fn example_l(
+  env: Env,
+  #[napi(ts_arg_type = "(step: string, total: number) => number | Promise<number>")]
+  hook: JsCallback<(&'static str, u32), u32>,
+) -> napi::Result<JsObject> {
+  env.spawn_local_promise(async move {
+    let mut total = 0;
+    for step in ["load", "transform", "emit"] {
+      let count = hook.call_async((step, total)).await?;
+      total += count;
+    }
+    Ok(total)
+  })
+}
+
+#[napi]
+pub fn example_m(
+  env: Env,
+  filter: JsRc<JsFunction>,
+) -> napi::Result<JsObject> {
+  let filter = JsCallbackHandle::<(u32,), bool>::new(filter)?;
+
+  env.spawn_thread(move || {
+    let mut accepted = vec![];
+    for value in 0..10 {
+      if filter.call_blocking((value,))? {
+        accepted.push(value);
+      }
+    }
+    Ok(move |_env| Ok(accepted))
+  })
+}
 
-// #[napi_async]
-// pub async fn example_d(
-//   env: Env,
-//   value: JsRc<JsString>,
-// ) -> napi::Result<JsUndefined> {
-//   task::sleep(Duration::from_millis(1000)).await;
-//   let v = value.get()?;
-//   env.console_log(&[&v])?;
-//   env.get_undefined()
-// }
+#[napi]
+pub fn example_n(
+  env: Env,
+  value: JsRc<JsObject>,
+  callback: JsRc<JsFunction>,
+) -> napi::Result<()> {
+  let value = value.downgrade()?;
+
+  env.spawn_local(async move {
+    while value.is_alive() {
+      task::sleep(Duration::from_millis(100)).await;
+    }
+    callback.borrow(&env)?.call_without_args(None)?;
+    Ok(())
+  })
+}
+
+#[napi]
+pub fn example_o(
+  env: Env,
+  value: JsRc<JsUnknown>,
+) -> napi::Result<JsObject> {
+  env.spawn_local_promise(async move {
+    task::sleep(Duration::from_millis(100)).await;
+    match value.cast::<JsFunction>() {
+      Ok(callback) => callback.into_inner(&env)?.call_without_args(None),
+      Err(_) => value.into_inner(&env),
+    }
+  })
+}
+
+#[napi]
+pub fn example_p(
+  env: Env,
+  #[napi(ts_arg_type = "any")] value: JsValueOwned,
+) -> napi::Result<JsObject> {
+  env.spawn_thread(move || {
+    let value = match value {
+      JsValueOwned::Array(mut items) => {
+        items.reverse();
+        JsValueOwned::Array(items)
+      }
+      value => value,
+    };
+    Ok(move |_env| Ok(value))
+  })
+}
+
+#[napi]
+pub fn example_q(
+  env: Env,
+  #[napi(ts_arg_type = "any")] value: Serialized,
+) -> napi::Result<JsObject> {
+  env.spawn_thread(move || {
+    println!("RS: {} bytes", value.0.len());
+    Ok(move |_env| Ok(value))
+  })
+}
+
+#[napi_async]
+pub async fn example_r(
+  name: String,
+  callback: JsCallback<(String,)>,
+) -> napi::Result<String> {
+  task::sleep(Duration::from_millis(100)).await;
+  callback.call((name.clone(),))?;
+  Ok(format!("Hello {}", name))
+}
+
+#[derive(serde::Deserialize)]
+pub struct ExampleOptions {
+  delay: u64,
+  message: String,
+}
+
+#[napi_async(serde)]
+pub async fn example_s(
+  #[napi(ts_arg_type = "{ delay: number, message: string }")] options: ExampleOptions,
+) -> napi::Result<String> {
+  task::sleep(Duration::from_millis(options.delay)).await;
+  Ok(options.message)
+}
+
+#[napi]
+pub struct ExampleCounter {
+  count: u32,
+}
+
+#[napi_async]
+#[napi]
+impl ExampleCounter {
+  #[napi(constructor)]
+  pub fn new(count: u32) -> Self {
+    Self { count }
+  }
+
+  #[napi_async]
+  pub async fn increment(
+    &mut self,
+    delay: u32,
+  ) -> napi::Result<u32> {
+    task::sleep(Duration::from_millis(delay as u64)).await;
+    self.count += 1;
+    Ok(self.count)
+  }
+
+  #[napi_async]
+  pub async fn get(&self) -> napi::Result<u32> {
+    task::sleep(Duration::from_millis(10)).await;
+    Ok(self.count)
+  }
+}
+
+#[napi_async(timeout = "1s", abortable, catch_unwind)]
+pub async fn example_u(delay: u32) -> napi::Result<u32> {
+  if delay == 0 {
+    panic!("Cannot wait for 0ms");
+  }
+  task::sleep(Duration::from_millis(delay as u64)).await;
+  Ok(delay)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ExampleError {
+  #[error("User {0} was not found")]
+  NotFound(u32),
+  #[error("Failed to load user")]
+  Io(#[from] std::io::Error),
+}
+
+impl ErrorCode for ExampleError {
+  fn code(&self) -> &str {
+    match self {
+      Self::NotFound(_) => "ERR_NOT_FOUND",
+      Self::Io(_) => "ERR_IO",
+    }
+  }
+}
+
+#[napi_async]
+pub async fn example_v(id: u32) -> std::result::Result<String, ExampleError> {
+  task::sleep(Duration::from_millis(100)).await;
+  match id {
+    1 => Ok("Alice".to_string()),
+    2 => Err(std::io::Error::from(std::io::ErrorKind::PermissionDenied).into()),
+    id => Err(ExampleError::NotFound(id)),
+  }
+}
+
+#[napi_async]
+pub async fn example_w(path: String) -> anyhow::Result<u32> {
+  use anyhow::Context;
+
+  let bytes = async_std::fs::read(&path)
+    .await
+    .with_context(|| format!("Failed to read {}", path))?;
+  Ok(bytes.len() as u32)
+}
+
+/// Counts up to `count`, waiting `delay` ms before each number
+#[napi_stream]
+pub async fn example_x(
+  count: u32,
+  delay: u32,
+) -> u32 {
+  for i in 1..=count {
+    task::sleep(Duration::from_millis(delay as u64)).await;
+    if i == 13 {
+      Err(napi::Error::from_reason("Unlucky number"))?;
+    }
+    yield_!(i);
+  }
+}
+
+#[napi_stream]
+pub fn example_y(words: Vec<String>) -> impl futures::Stream<Item = napi::Result<String>> {
+  futures::stream::iter(words).then(|word| async move {
+    task::sleep(Duration::from_millis(10)).await;
+    Ok(word.to_uppercase())
+  })
+}
+
+#[napi(object)]
+pub struct ExampleChecksum {
+  pub length: u32,
+  pub checksum: u32,
+}
+
+/// Checksums `input` on another thread
+#[napi_thread]
+pub fn example_z(input: Vec<u8>) -> napi::Result<ExampleChecksum> {
+  if input.is_empty() {
+    return Err(napi::Error::from_reason("Cannot checksum an empty input"));
+  }
+  thread::sleep(Duration::from_millis(100));
+  let checksum = input
+    .iter()
+    .fold(0u32, |sum, byte| sum.wrapping_mul(31).wrapping_add(*byte as u32));
+  Ok(ExampleChecksum {
+    length: input.len() as u32,
+    checksum,
+  })
+}
@@ -0,0 +1,6 @@
+import napi from '@workspace/addon'
+
+for await (const value of napi.exampleE()) {
+  console.log('JS', value)
+  if (value === 5) break
+}
@@ -0,0 +1,14 @@
+import napi from '@workspace/addon'
+
+async function* values() {
+  try {
+    for (let i = 0; i < 10; i++) {
+      await new Promise(res => setTimeout(res, 500))
+      yield i
+    }
+  } finally {
+    console.log('JS: iterator closed')
+  }
+}
+
+await napi.exampleF(values(), (value) => console.log('Rust got', value))
@@ -0,0 +1,28 @@
+import { Readable, Writable } from 'node:stream'
+import napi from '@workspace/addon'
+
+// Destroying the source mid-copy rejects rather than waiting for an "end" that never comes
+const readable = new Readable({ read() {} })
+const writable = new Writable({ write(_chunk, _encoding, callback) { callback() } })
+
+const copied = napi.exampleG(readable, writable)
+readable.push('Hello ')
+setTimeout(() => readable.destroy(), 100)
+
+try {
+  await copied
+} catch (error) {
+  console.log('Destroyed:', error.message)
+}
+
+// A source destroyed with an error rejects with that error
+const failing = new Readable({ read() {} })
+const failed = napi.exampleG(failing, new Writable({ write(_chunk, _encoding, callback) { callback() } }))
+failing.push('Hello ')
+setTimeout(() => failing.destroy(new Error('source failed')), 100)
+
+try {
+  await failed
+} catch (error) {
+  console.log('Errored:', error.message)
+}
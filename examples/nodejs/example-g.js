@@ -0,0 +1,15 @@
+import fs from 'node:fs'
+import os from 'node:os'
+import path from 'node:path'
+import napi from '@workspace/addon'
+
+const target = path.join(os.tmpdir(), 'napi-example-g.txt')
+const readable = fs.createReadStream(new URL(import.meta.url), { highWaterMark: 64 })
+const writable = fs.createWriteStream(target)
+
+const copied = await napi.exampleG(readable, writable)
+console.log('Copied', copied, 'bytes to', target)
+
+for await (const chunk of napi.exampleH()) {
+  process.stdout.write(chunk)
+}
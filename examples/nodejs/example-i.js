@@ -0,0 +1,11 @@
+import napi from '@workspace/addon'
+
+const response = new Response(napi.exampleI())
+console.log(await response.text())
+
+const writable = napi.exampleJ((line) => console.log('JS', line))
+const writer = writable.getWriter()
+for (const word of ['hello', 'web', 'streams']) {
+  await writer.write(word)
+}
+await writer.close()
@@ -0,0 +1,10 @@
+import { EventEmitter } from 'node:events'
+import napi from '@workspace/addon'
+
+const emitter = new EventEmitter()
+emitter.on('reply', (message) => console.log('JS', message))
+
+napi.exampleK(emitter)
+for (const message of ['hello', 'from', 'events', 'ignored']) {
+  emitter.emit('message', message)
+}
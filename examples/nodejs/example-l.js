@@ -0,0 +1,8 @@
+import napi from '@workspace/addon'
+
+const total = await napi.exampleL(async (step, total) => {
+  console.log('JS', step, total)
+  await new Promise((resolve) => setTimeout(resolve, 100))
+  return step.length
+})
+console.log('Total', total)
@@ -0,0 +1,8 @@
+import napi from '@workspace/addon'
+
+const accepted = await napi.exampleM(async (value) => {
+  console.log('JS', value)
+  await new Promise((resolve) => setTimeout(resolve, 50))
+  return value % 3 === 0
+})
+console.log('Accepted', accepted)
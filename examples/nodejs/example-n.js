@@ -0,0 +1,10 @@
+// Run with: node --expose-gc example-n.js
+import napi from '@workspace/addon'
+
+let value = { hello: 'world' }
+napi.exampleN(value, () => console.log('JS value was collected'))
+
+setTimeout(() => {
+  value = undefined
+  globalThis.gc()
+}, 500)
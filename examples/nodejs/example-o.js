@@ -0,0 +1,4 @@
+import napi from '@workspace/addon'
+
+console.log(await napi.exampleO(() => 'Called by Rust'))
+console.log(await napi.exampleO('Returned by Rust'))
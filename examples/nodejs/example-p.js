@@ -0,0 +1,19 @@
+import napi from '@workspace/addon'
+
+const value = await napi.exampleP([
+  'hello',
+  42n,
+  new Date(0),
+  Buffer.from('bytes'),
+  new Map([['key', { nested: [1, 2, 3] }]]),
+  new Set([true, null, undefined]),
+])
+console.log(value)
+
+try {
+  const cycle = { name: 'cycle' }
+  cycle.self = cycle
+  await napi.exampleP(cycle)
+} catch (error) {
+  console.log('Error:', error.message)
+}
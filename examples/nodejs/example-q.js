@@ -0,0 +1,7 @@
+import napi from '@workspace/addon'
+
+const value = { list: new Float64Array([1.5, 2.5]), pattern: /napi/g, when: new Date(0) }
+value.self = value
+
+const copy = await napi.exampleQ(value)
+console.log(copy, copy.self === copy)
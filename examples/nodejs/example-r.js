@@ -0,0 +1,4 @@
+import napi from '@workspace/addon'
+
+console.log(await napi.exampleR('World', (name) => console.log('JS', name)))
+console.log(await napi.exampleS({ delay: 100, message: 'Deserialized with serde' }))
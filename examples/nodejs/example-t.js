@@ -0,0 +1,10 @@
+import napi from '@workspace/addon'
+
+const counter = new napi.ExampleCounter(0)
+
+console.log(await counter.increment(100))
+console.log(await Promise.all([counter.get(), counter.get()]))
+
+// Only one call can borrow the counter mutably
+const results = await Promise.allSettled([counter.increment(100), counter.increment(100)])
+console.log(results.map(result => result.value ?? result.reason.message))
@@ -0,0 +1,13 @@
+import napi from '@workspace/addon'
+
+console.log(await napi.exampleU(100))
+
+const controller = new AbortController()
+setTimeout(() => controller.abort(), 100)
+
+const results = await Promise.allSettled([
+  napi.exampleU(2000),
+  napi.exampleU(500, controller.signal),
+  napi.exampleU(0),
+])
+console.log(results.map(result => `${result.reason.name}: ${result.reason.message}`))
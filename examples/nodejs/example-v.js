@@ -0,0 +1,17 @@
+import napi from '@workspace/addon'
+
+console.log(await napi.exampleV(1))
+
+for (const id of [2, 3]) {
+  try {
+    await napi.exampleV(id)
+  } catch (error) {
+    console.log(error.code, error.message, '<-', error.cause?.message)
+  }
+}
+
+try {
+  await napi.exampleW('./does-not-exist.txt')
+} catch (error) {
+  console.log(error.message, '<-', error.cause?.message)
+}
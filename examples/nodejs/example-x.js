@@ -0,0 +1,21 @@
+import napi from '@workspace/addon'
+
+for await (const value of napi.exampleX(5, 100)) {
+  console.log(value)
+}
+
+for await (const value of napi.exampleX(1000, 10)) {
+  if (value === 3) break
+}
+
+try {
+  for await (const value of napi.exampleX(20, 10)) {}
+} catch (error) {
+  console.log(error.message)
+}
+
+const words = []
+for await (const word of napi.exampleY(['hello', 'world'])) {
+  words.push(word)
+}
+console.log(words)
@@ -0,0 +1,9 @@
+import napi from '@workspace/addon'
+
+console.log(await napi.exampleZ([1, 2, 3, 4]))
+
+try {
+  await napi.exampleZ([])
+} catch (error) {
+  console.log(error.message)
+}
@@ -1,104 +1,569 @@
+mod options;
+mod stream;
+mod thread;
+mod typescript;
+
 use proc_macro::TokenStream;
+use quote::format_ident;
 use quote::quote;
-use quote::ToTokens;
-use quote::TokenStreamExt;
+use syn::punctuated::Punctuated;
+use syn::Attribute;
+use syn::FnArg;
+use syn::GenericArgument;
 use syn::Ident;
+use syn::ImplItem;
+use syn::ImplItemFn;
+use syn::Item;
 use syn::ItemFn;
+use syn::ItemImpl;
+use syn::LitStr;
+use syn::Meta;
+use syn::PathArguments;
 use syn::ReturnType;
+use syn::Signature;
+use syn::Token;
+use syn::Type;
+use syn::Visibility;
+
+use self::options::parse_options;
+use self::options::Options;
+use self::typescript::TsArg;
+
+/// napi handle types that are only valid until the current scope closes.
+/// Parameters of these types are wrapped in a JsRc so they can be held across awaits
+const JS_HANDLE_TYPES: &[&str] = &[
+  "JsArrayBuffer",
+  "JsBigInt",
+  "JsBoolean",
+  "JsBuffer",
+  "JsDataView",
+  "JsDate",
+  "JsExternal",
+  "JsFunction",
+  "JsGlobal",
+  "JsNull",
+  "JsNumber",
+  "JsObject",
+  "JsString",
+  "JsSymbol",
+  "JsTimeout",
+  "JsTypedArray",
+  "JsUndefined",
+  "JsUnknown",
+];
 
 #[proc_macro_attribute]
 pub fn napi_async(
-  _args: TokenStream,
+  args: TokenStream,
   input: TokenStream,
 ) -> TokenStream {
-  convert(input.into())
+  expand(args.into(), input.into())
     .unwrap_or_else(|err| err.into_compile_error())
     .into()
 }
 
-fn convert(input: proc_macro2::TokenStream) -> Result<proc_macro2::TokenStream, syn::Error> {
-  let mut func = syn::parse2::<ItemFn>(input)?;
+/// Exports a function returning a JavaScript async iterator from a function returning
+/// `impl Stream<Item = napi::Result<T>>`, or an async function using `yield_!(value)`
+#[proc_macro_attribute]
+pub fn napi_stream(
+  args: TokenStream,
+  input: TokenStream,
+) -> TokenStream {
+  expand_stream(args.into(), input.into())
+    .unwrap_or_else(|err| err.into_compile_error())
+    .into()
+}
 
-  if func.sig.asyncness.is_none() {
-    return Err(syn::Error::new_spanned(func, "Failed to do the thing"));
+fn expand_stream(
+  args: proc_macro2::TokenStream,
+  input: proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+  match syn::parse2::<Item>(input)? {
+    Item::Fn(func) => stream::convert_stream(parse_options("napi_stream", args)?, func),
+    item => Err(syn::Error::new_spanned(
+      item,
+      "#[napi_stream] can only be used on functions",
+    )),
   }
+}
 
-  let raw_inputs = &func.sig.inputs;
-  let mut input_names = proc_macro2::TokenStream::new();
-  let mut pre_body = proc_macro2::TokenStream::new();
+/// Exports a function that runs on a new thread and returns a Promise of its output,
+/// which is converted on the JavaScript thread
+#[proc_macro_attribute]
+pub fn napi_thread(
+  args: TokenStream,
+  input: TokenStream,
+) -> TokenStream {
+  expand_thread(args.into(), input.into())
+    .unwrap_or_else(|err| err.into_compile_error())
+    .into()
+}
 
-  let mut insert_env = proc_macro2::TokenStream::new();
-  let mut has_env = false;
+fn expand_thread(
+  args: proc_macro2::TokenStream,
+  input: proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+  match syn::parse2::<Item>(input)? {
+    Item::Fn(func) => thread::convert_thread(parse_options("napi_thread", args)?, func),
+    item => Err(syn::Error::new_spanned(
+      item,
+      "#[napi_thread] can only be used on functions",
+    )),
+  }
+}
+
+fn expand(
+  args: proc_macro2::TokenStream,
+  input: proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+  match syn::parse2::<Item>(input)? {
+    Item::Fn(func) => convert(parse_options("napi_async", args)?, func),
+    Item::Impl(item) => convert_impl(args, item),
+    item => Err(syn::Error::new_spanned(
+      item,
+      "#[napi_async] can only be used on functions and impl blocks",
+    )),
+  }
+}
+
+fn convert(
+  options: Options,
+  mut func: ItemFn,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+  check_signature(&func.sig)?;
+
+  if let Some(FnArg::Receiver(receiver)) = func.sig.inputs.first() {
+    return Err(syn::Error::new_spanned(
+      receiver,
+      "#[napi_async] methods must be in an impl block that is also marked with #[napi_async]",
+    ));
+  }
+
+  let ts_return_type = promise_ts_type(&options, &func.sig);
+  let Inputs {
+    wrapper_inputs,
+    call_args,
+    napi_args,
+  } = convert_inputs(&options, &mut func.sig, ts_return_type)?;
+  let output = convert_output(&options, &func.sig.output);
+  let spawn = spawn(&options);
+  let (ident, inner_ident) = rename_inner(&mut func.sig);
+  let vis = std::mem::replace(&mut func.vis, Visibility::Inherited);
+  let docs = docs(&func.attrs);
 
-  for input in raw_inputs.iter() {
-    match &input {
-      syn::FnArg::Receiver(_r) => continue,
-      syn::FnArg::Typed(t) => {
-        let pat = &*t.pat;
-        if let syn::Type::Path(p) = &*t.ty {
-          if let Some(segment) = p.path.segments.last() {
-            if segment.ident == "Env" {
-              has_env = true;
-            } else if segment.ident == "JsString"
-              || segment.ident == "JsUnknown"
-              || segment.ident == "JsUndefined"
-              || segment.ident == "JsNull"
-              || segment.ident == "JsBoolean"
-              || segment.ident == "JsBuffer"
-              || segment.ident == "JsArrayBuffer"
-              || segment.ident == "JsTypedArray"
-              || segment.ident == "JsDataView"
-              || segment.ident == "JsNumber"
-              || segment.ident == "JsString"
-              || segment.ident == "JsObject"
-              || segment.ident == "JsGlobal"
-              || segment.ident == "JsDate"
-              || segment.ident == "JsFunction"
-              || segment.ident == "JsExternal"
-              || segment.ident == "JsSymbol"
-              || segment.ident == "JsTimeout"
-              || segment.ident == "JSON"
-            {
-              pre_body.append_all(
-                quote! {let #pat = #pat.into_rc(&env).unwrap().into_inner(&env).unwrap();
-                },
-              );
-            };
-          }
-        }
-        input_names.append_all(pat.to_token_stream());
-        input_names.append_all(quote! {,});
+  Ok(quote! {
+    #func
+
+    #(#docs)*
+    #[napi_derive::napi(#napi_args)]
+    #vis fn #ident(#wrapper_inputs) -> napi::Result<napi::JsObject> {
+      let fut = async move {
+        let output = #inner_ident(#(#call_args),*).await;
+        #output
+      };
+      #spawn
+    }
+  })
+}
+
+/// Converts the #[napi_async] methods of an impl block. The exported wrappers
+/// are emitted in a separate #[napi] impl block so it does not matter if napi-derive
+/// expands the original impl block before or after this macro
+fn convert_impl(
+  args: proc_macro2::TokenStream,
+  mut item: ItemImpl,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+  if !args.is_empty() {
+    return Err(syn::Error::new_spanned(
+      args,
+      "options are set on the #[napi_async] methods of the impl block",
+    ));
+  }
+
+  let mut wrappers = Vec::<ImplItemFn>::new();
+
+  for impl_item in item.items.iter_mut() {
+    let ImplItem::Fn(method) = impl_item else {
+      continue;
+    };
+
+    let Some(index) = method.attrs.iter().position(|attr| {
+      attr
+        .path()
+        .segments
+        .last()
+        .is_some_and(|s| s.ident == "napi_async")
+    }) else {
+      continue;
+    };
+
+    let options = match method.attrs.remove(index).meta {
+      Meta::Path(_) => Options::default(),
+      Meta::List(list) => parse_options("napi_async", list.tokens)?,
+      meta @ Meta::NameValue(_) => {
+        return Err(syn::Error::new_spanned(
+          meta,
+          "expected #[napi_async] or #[napi_async(...)]",
+        ))
       }
+    };
+
+    wrappers.push(convert_method(options, method)?);
+  }
+
+  if wrappers.is_empty() {
+    return Ok(quote! { #item });
+  }
+
+  let self_ty = &item.self_ty;
+
+  Ok(quote! {
+    #item
+
+    #[napi_derive::napi]
+    impl #self_ty {
+      #(#wrappers)*
     }
+  })
+}
+
+/// Converts a method in place to the async implementation and returns the exported wrapper.
+/// The wrapper holds the class instance in a JsInstance so `self` is kept alive across awaits
+fn convert_method(
+  options: Options,
+  method: &mut ImplItemFn,
+) -> Result<ImplItemFn, syn::Error> {
+  check_signature(&method.sig)?;
+
+  if let Some(namespace) = &options.namespace {
+    return Err(syn::Error::new_spanned(
+      namespace,
+      "namespace is not supported on methods",
+    ));
   }
 
-  if !has_env {
-    insert_env = quote! {env: Env,}
+  let receiver = match method.sig.inputs.first() {
+    Some(FnArg::Receiver(receiver)) if receiver.reference.is_none() => {
+      return Err(syn::Error::new_spanned(
+        receiver,
+        "#[napi_async] methods cannot take self by value, use &self or &mut self",
+      ));
+    }
+    Some(FnArg::Receiver(receiver)) => Some(receiver.mutability.is_some()),
+    _ => None,
+  };
+
+  let ts_return_type = promise_ts_type(&options, &method.sig);
+  let Inputs {
+    mut wrapper_inputs,
+    mut call_args,
+    napi_args,
+  } = convert_inputs(&options, &mut method.sig, ts_return_type)?;
+  let output = convert_output(&options, &method.sig.output);
+  let spawn = spawn(&options);
+  let (ident, inner_ident) = rename_inner(&mut method.sig);
+  let vis = std::mem::replace(&mut method.vis, Visibility::Inherited);
+  let docs = docs(&method.attrs);
+
+  let Some(mutable) = receiver else {
+    return Ok(syn::parse_quote! {
+      #(#docs)*
+      #[napi(#napi_args)]
+      #vis fn #ident(#wrapper_inputs) -> napi::Result<napi::JsObject> {
+        let fut = async move {
+          let output = Self::#inner_ident(#(#call_args),*).await;
+          #output
+        };
+        #spawn
+      }
+    });
+  };
+
+  wrapper_inputs.push(syn::parse_quote! { this: napi::bindgen_prelude::This });
+  // Borrowing in the future rejects the promise if the instance is already borrowed
+  let borrow = if mutable {
+    call_args.insert(0, quote! { &mut instance });
+    quote! { let mut instance = instance.borrow_mut()?; }
+  } else {
+    call_args.insert(0, quote! { &instance });
+    quote! { let instance = instance.borrow()?; }
+  };
+
+  Ok(syn::parse_quote! {
+    #(#docs)*
+    #[napi(#napi_args)]
+    #vis fn #ident(&self, #wrapper_inputs) -> napi::Result<napi::JsObject> {
+      // Safety: Synchronous methods of the class must not take &mut self, see the README
+      let instance = unsafe { napi_ext::JsInstance::new(&env, this, self) }?;
+      let fut = async move {
+        #borrow
+        let output = Self::#inner_ident(#(#call_args),*).await;
+        #output
+      };
+      #spawn
+    }
+  })
+}
+
+/// Checks the parts of a signature that cannot be exported
+fn check_signature(sig: &Signature) -> Result<(), syn::Error> {
+  if sig.asyncness.is_none() {
+    return Err(syn::Error::new_spanned(
+      sig.fn_token,
+      "#[napi_async] can only be used on async functions",
+    ));
   }
 
-  if let ReturnType::Default = func.sig.output {
-    let new_output = syn::parse2::<ItemFn>(quote! {fn x() -> () { () }})?;
-    func.sig.output = new_output.sig.output;
+  if !sig.generics.params.is_empty() {
+    return Err(syn::Error::new_spanned(
+      &sig.generics,
+      "#[napi_async] functions cannot be generic",
+    ));
   }
 
-  let ident = func.sig.ident.clone();
-  func.sig.ident = Ident::new(&format!("async_local_{}", ident), ident.span());
-  let new_ident = &func.sig.ident;
+  Ok(())
+}
 
-  let ret = match &func.sig.output {
-    syn::ReturnType::Default => quote! {napi::Result<napi::JsUndefined>},
-    syn::ReturnType::Type(_, v) => quote!(#v),
+/// Converts `output`, the output of the async function, into a napi::Result.
+/// Errors are converted with the first of the conversions in napi_ext::rejection that applies
+fn convert_output(
+  options: &Options,
+  output: &ReturnType,
+) -> proc_macro2::TokenStream {
+  let returns_result = match output {
+    ReturnType::Type(_, ty) => options.result || is_result(ty),
+    ReturnType::Default => false,
   };
 
-  Ok(quote! {
-    #func
+  if !returns_result {
+    return quote! { napi::Result::Ok(output) };
+  }
 
-    #[napi_derive::napi]
-    fn #ident(#insert_env #raw_inputs) -> napi::Result<JsObject> {
-      #pre_body
+  quote! {
+    match output {
+      Ok(value) => napi::Result::Ok(value),
+      Err(error) => {
+        #[allow(unused_imports)]
+        use napi_ext::rejection::*;
+        Err((&&&&napi_ext::rejection::Rejection::new(error)).reject(&env))
+      }
+    }
+  }
+}
 
-      let fut = #new_ident(#input_names);
-      env.spawn_local_promise(fut)
+/// Wraps the future `fut` according to the options and spawns it on the local runtime
+fn spawn(options: &Options) -> proc_macro2::TokenStream {
+  let mut spawn = proc_macro2::TokenStream::new();
+
+  if options.abortable {
+    spawn.extend(quote! { let fut = napi_ext::with_abort_signal(signal, fut); });
+  }
+  if let Some(timeout) = options.timeout {
+    spawn.extend(quote! {
+      let fut = napi_ext::with_timeout(env, std::time::Duration::from_millis(#timeout), fut);
+    });
+  }
+  if options.catch_unwind {
+    spawn.extend(quote! { let fut = napi_ext::catch_unwind(fut); });
+  }
+
+  spawn.extend(quote! { napi_ext::SpawnLocalExt::spawn_local_promise(&env, fut) });
+  spawn
+}
+
+/// The parameters of an exported wrapper
+struct Inputs {
+  wrapper_inputs: Punctuated<FnArg, Token![,]>,
+  /// The arguments the wrapper passes to the async function
+  call_args: Vec<proc_macro2::TokenStream>,
+  /// The TypeScript signature of the wrapper for #[napi]
+  napi_args: proc_macro2::TokenStream,
+}
+
+/// Converts the parameters of the async function to the parameters of the exported wrapper.
+/// A receiver is left to the caller
+fn convert_inputs(
+  options: &Options,
+  sig: &mut Signature,
+  ts_return_type: String,
+) -> Result<Inputs, syn::Error> {
+  let mut wrapper_inputs = Punctuated::<FnArg, Token![,]>::new();
+  let mut call_args = Vec::<proc_macro2::TokenStream>::new();
+  let mut ts_args = Vec::<TsArg>::new();
+  let mut has_env = false;
+
+  for (i, input) in sig.inputs.iter_mut().enumerate() {
+    let FnArg::Typed(input) = input else {
+      continue;
+    };
+
+    let ty = &mut *input.ty;
+
+    if is_env(ty) {
+      has_env = true;
+      wrapper_inputs.push(syn::parse_quote! { env: #ty });
+      call_args.push(quote! { env });
+      continue;
+    }
+
+    if let Type::Reference(reference) = ty {
+      return Err(syn::Error::new_spanned(
+        reference,
+        "borrowed parameters cannot be held by the returned future or stream, use an owned type such as String or JsRc<T>",
+      ));
+    }
+
+    // The wrapper's parameters are renamed so its signature is written for TypeScript here.
+    // #[napi(ts_arg_type)] cannot stay on the async function's parameters
+    let (ts_type, optional) = typescript::ts_type(ty, false);
+    ts_args.push(TsArg {
+      name: typescript::ts_arg_name(&input.pat, i),
+      ts_type: ts_arg_type(&std::mem::take(&mut input.attrs))?.unwrap_or(ts_type),
+      optional,
+    });
+
+    let arg = format_ident!("arg{}", i);
+    if is_js_handle(ty) {
+      *ty = syn::parse_quote! { napi_ext::JsRc<#ty> };
+      wrapper_inputs.push(syn::parse_quote! { #arg: #ty });
+      call_args.push(quote! { #arg });
+    } else if options.serde && !is_js_type(ty) {
+      wrapper_inputs.push(syn::parse_quote! { #arg: napi_ext::SerdeValue<#ty> });
+      call_args.push(quote! { #arg.0 });
+    } else {
+      wrapper_inputs.push(syn::parse_quote! { #arg: #ty });
+      call_args.push(quote! { #arg });
     }
+  }
+
+  if !has_env {
+    // napi-derive only recognises the unqualified Env type
+    wrapper_inputs.insert(0, syn::parse_quote! { env: Env });
+  }
+
+  if options.abortable {
+    wrapper_inputs.push(syn::parse_quote! { signal: Option<napi_ext::JsRc<napi::JsObject>> });
+    ts_args.push(TsArg {
+      name: "signal".to_string(),
+      ts_type: "AbortSignal".to_string(),
+      optional: true,
+    });
+  }
+
+  let ts_args_type = typescript::ts_args(&ts_args);
+
+  let mut napi_args = quote! { ts_args_type = #ts_args_type, ts_return_type = #ts_return_type };
+  if let Some(js_name) = &options.js_name {
+    napi_args.extend(quote! { , js_name = #js_name });
+  }
+  if let Some(namespace) = &options.namespace {
+    napi_args.extend(quote! { , namespace = #namespace });
+  }
+
+  Ok(Inputs {
+    wrapper_inputs,
+    call_args,
+    napi_args,
   })
 }
+
+fn promise_ts_type(
+  options: &Options,
+  sig: &Signature,
+) -> String {
+  match &sig.output {
+    ReturnType::Type(_, ty) => format!("Promise<{}>", resolved_ts_type(options, ty)),
+    ReturnType::Default => "Promise<void>".to_string(),
+  }
+}
+
+/// Reads the TypeScript type of a parameter set with #[napi(ts_arg_type = "...")]
+fn ts_arg_type(attrs: &[Attribute]) -> Result<Option<String>, syn::Error> {
+  let mut ts_type = None;
+
+  for attr in attrs {
+    if !attr.path().is_ident("napi") {
+      return Err(syn::Error::new_spanned(
+        attr,
+        "unsupported attribute on a #[napi_async] parameter",
+      ));
+    }
+
+    attr.parse_nested_meta(|meta| {
+      if meta.path.is_ident("ts_arg_type") {
+        ts_type = Some(meta.value()?.parse::<LitStr>()?.value());
+        Ok(())
+      } else {
+        Err(meta.error("unsupported napi option on a #[napi_async] parameter"))
+      }
+    })?;
+  }
+
+  Ok(ts_type)
+}
+
+/// Doc comments are copied to the exported wrapper so they are included in the TypeScript definitions
+fn docs(attrs: &[Attribute]) -> Vec<&Attribute> {
+  attrs
+    .iter()
+    .filter(|attr| attr.path().is_ident("doc"))
+    .collect()
+}
+
+/// Renames the async function so the exported wrapper can take its name
+fn rename_inner(sig: &mut Signature) -> (Ident, Ident) {
+  let ident = sig.ident.clone();
+  sig.ident = Ident::new(&format!("async_local_{}", ident), ident.span());
+  (ident, sig.ident.clone())
+}
+
+/// Gets the name of a type without its path or generics
+fn type_ident(ty: &Type) -> Option<&Ident> {
+  match ty {
+    Type::Path(path) if path.qself.is_none() => path.path.segments.last().map(|s| &s.ident),
+    _ => None,
+  }
+}
+
+/// Matches `Result` at the end of any path, such as `napi::Result` or `anyhow::Result`.
+/// Aliases with other names need the `result` option
+fn is_result(ty: &Type) -> bool {
+  type_ident(ty).is_some_and(|ident| ident == "Result")
+}
+
+/// The TypeScript type of the value a function returning `ty` resolves with
+fn resolved_ts_type(
+  options: &Options,
+  ty: &Type,
+) -> String {
+  let ok_type = match ty {
+    Type::Path(path) if options.result => {
+      path
+        .path
+        .segments
+        .last()
+        .and_then(|segment| match &segment.arguments {
+          PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+          }),
+          _ => None,
+        })
+    }
+    _ => None,
+  };
+  typescript::ts_type(ok_type.unwrap_or(ty), true).0
+}
+
+fn is_env(ty: &Type) -> bool {
+  type_ident(ty).is_some_and(|ident| ident == "Env")
+}
+
+fn is_js_handle(ty: &Type) -> bool {
+  type_ident(ty).is_some_and(|ident| JS_HANDLE_TYPES.iter().any(|name| ident == name))
+}
+
+/// JavaScript values and napi_ext wrappers such as JsRc<T>
+fn is_js_type(ty: &Type) -> bool {
+  type_ident(ty).is_some_and(|ident| ident.to_string().starts_with("Js"))
+}
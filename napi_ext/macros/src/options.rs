@@ -0,0 +1,130 @@
+use syn::meta::ParseNestedMeta;
+use syn::parse::Parser;
+use syn::LitStr;
+
+/// Options of `#[napi_async(...)]`, `#[napi_stream(...)]` and `#[napi_thread(...)]`
+#[derive(Default)]
+pub struct Options {
+  /// Deserialize owned parameters with serde
+  pub serde: bool,
+  /// Forwarded to napi-derive
+  pub js_name: Option<LitStr>,
+  /// Forwarded to napi-derive, only valid on functions
+  pub namespace: Option<LitStr>,
+  /// Treats the return type as a Result, for aliases such as `type Res<T> = Result<T, MyError>`
+  pub result: bool,
+  /// Rejects with a TimeoutError after this many milliseconds
+  pub timeout: Option<u64>,
+  /// Adds an optional AbortSignal parameter that cancels the future
+  pub abortable: bool,
+  /// Rejects rather than unwinding when the future panics
+  pub catch_unwind: bool,
+}
+
+/// Parses the options of the attribute `attr`. The options that wrap a future
+/// are only supported by `#[napi_async]`
+pub fn parse_options(
+  attr: &str,
+  args: proc_macro2::TokenStream,
+) -> Result<Options, syn::Error> {
+  let mut options = Options::default();
+  let is_async = attr == "napi_async";
+
+  let parser = syn::meta::parser(|meta| {
+    if meta.path.is_ident("serde") {
+      set_flag(attr, &meta, &mut options.serde)
+    } else if meta.path.is_ident("result") {
+      set_flag(attr, &meta, &mut options.result)
+    } else if is_async && meta.path.is_ident("abortable") {
+      set_flag(attr, &meta, &mut options.abortable)
+    } else if is_async && meta.path.is_ident("catch_unwind") {
+      set_flag(attr, &meta, &mut options.catch_unwind)
+    } else if meta.path.is_ident("js_name") {
+      set_value(attr, &meta, &mut options.js_name)
+    } else if meta.path.is_ident("namespace") {
+      set_value(attr, &meta, &mut options.namespace)
+    } else if is_async && meta.path.is_ident("timeout") {
+      let mut timeout = None;
+      set_value(attr, &meta, &mut timeout)?;
+      if options.timeout.is_some() {
+        return Err(meta.error(format!("duplicate {} option", attr)));
+      }
+      options.timeout = timeout.as_ref().map(parse_duration).transpose()?;
+      Ok(())
+    } else if is_async {
+      Err(meta.error(format!(
+        "unsupported {} option, expected one of serde, result, js_name, namespace, timeout, abortable or catch_unwind",
+        attr
+      )))
+    } else {
+      Err(meta.error(format!(
+        "unsupported {} option, expected one of serde, result, js_name or namespace",
+        attr
+      )))
+    }
+  });
+  parser.parse2(args)?;
+
+  Ok(options)
+}
+
+fn set_flag(
+  attr: &str,
+  meta: &ParseNestedMeta<'_>,
+  flag: &mut bool,
+) -> Result<(), syn::Error> {
+  if *flag {
+    return Err(meta.error(format!("duplicate {} option", attr)));
+  }
+  *flag = true;
+  Ok(())
+}
+
+fn set_value(
+  attr: &str,
+  meta: &ParseNestedMeta<'_>,
+  value: &mut Option<LitStr>,
+) -> Result<(), syn::Error> {
+  if value.is_some() {
+    return Err(meta.error(format!("duplicate {} option", attr)));
+  }
+  *value = Some(meta.value()?.parse()?);
+  Ok(())
+}
+
+/// Parses a duration such as "5s" into milliseconds
+fn parse_duration(lit: &LitStr) -> Result<u64, syn::Error> {
+  let value = lit.value();
+  let split = value
+    .find(|c: char| !c.is_ascii_digit())
+    .unwrap_or(value.len());
+  let (amount, unit) = value.split_at(split);
+
+  let multiplier = match unit.trim() {
+    "ms" => 1,
+    "s" => 1000,
+    "m" => 60 * 1000,
+    _ => 0,
+  };
+
+  let Some(millis) = amount
+    .parse::<u64>()
+    .ok()
+    .filter(|_| multiplier != 0)
+    .map(|amount| amount.checked_mul(multiplier))
+  else {
+    return Err(syn::Error::new_spanned(
+      lit,
+      "expected a duration such as \"500ms\", \"5s\" or \"1m\"",
+    ));
+  };
+
+  // setTimeout fires at once for delays that do not fit in an i32
+  match millis {
+    Some(millis) if millis <= i32::MAX as u64 => Ok(millis),
+    _ => Err(syn::Error::new_spanned(
+      lit,
+      format!("the timeout cannot be longer than {}ms", i32::MAX),
+    )),
+  }
+}
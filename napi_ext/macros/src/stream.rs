@@ -0,0 +1,225 @@
+use quote::quote;
+use syn::visit_mut::VisitMut;
+use syn::Expr;
+use syn::FnArg;
+use syn::GenericArgument;
+use syn::ItemFn;
+use syn::PathArguments;
+use syn::ReturnType;
+use syn::Stmt;
+use syn::Type;
+use syn::TypeParamBound;
+use syn::Visibility;
+
+use crate::convert_inputs;
+use crate::docs;
+use crate::is_result;
+use crate::options::Options;
+use crate::rename_inner;
+use crate::resolved_ts_type;
+use crate::Inputs;
+
+/// Converts a function returning `impl Stream<Item = napi::Result<T>>`, or an async function
+/// yielding values with `yield_!(value)`, into an exported function returning an async iterator
+pub fn convert_stream(
+  options: Options,
+  mut func: ItemFn,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+  if let Some(FnArg::Receiver(receiver)) = func.sig.inputs.first() {
+    return Err(syn::Error::new_spanned(
+      receiver,
+      "#[napi_stream] can only be used on functions",
+    ));
+  }
+
+  if !func.sig.generics.params.is_empty() {
+    return Err(syn::Error::new_spanned(
+      &func.sig.generics,
+      "#[napi_stream] functions cannot be generic",
+    ));
+  }
+
+  let item = if func.sig.asyncness.is_some() {
+    into_generator(&mut func)?
+  } else {
+    stream_item(&options, &func.sig.output)?
+  };
+
+  let ts_return_type = format!(
+    "AsyncIterableIterator<{}>",
+    resolved_ts_type(&options, &item)
+  );
+
+  let Inputs {
+    wrapper_inputs,
+    call_args,
+    napi_args,
+  } = convert_inputs(&options, &mut func.sig, ts_return_type)?;
+  let (ident, inner_ident) = rename_inner(&mut func.sig);
+  let vis = std::mem::replace(&mut func.vis, Visibility::Inherited);
+  let docs = docs(&func.attrs);
+
+  Ok(quote! {
+    #func
+
+    #(#docs)*
+    #[napi_derive::napi(#napi_args)]
+    #vis fn #ident(#wrapper_inputs) -> napi::Result<napi::JsObject> {
+      napi_ext::stream::to_async_iterator(&env, #inner_ident(#(#call_args),*))
+    }
+  })
+}
+
+/// Gets `napi::Result<T>` from a return type of `impl Stream<Item = napi::Result<T>>`
+fn stream_item(
+  options: &Options,
+  output: &ReturnType,
+) -> Result<Type, syn::Error> {
+  let error = |span: &dyn quote::ToTokens| {
+    syn::Error::new_spanned(
+      span,
+      "#[napi_stream] functions must be async functions using yield_!(value) or return impl Stream<Item = napi::Result<T>>",
+    )
+  };
+
+  let ReturnType::Type(_, ty) = output else {
+    return Err(error(output));
+  };
+  let Type::ImplTrait(impl_trait) = &**ty else {
+    return Err(error(ty));
+  };
+
+  for bound in impl_trait.bounds.iter() {
+    let TypeParamBound::Trait(bound) = bound else {
+      continue;
+    };
+    let Some(segment) = bound.path.segments.last() else {
+      continue;
+    };
+    if segment.ident != "Stream" {
+      continue;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+      continue;
+    };
+    for arg in args.args.iter() {
+      let GenericArgument::AssocType(assoc) = arg else {
+        continue;
+      };
+      if assoc.ident != "Item" {
+        continue;
+      }
+      if !options.result && !is_result(&assoc.ty) {
+        return Err(syn::Error::new_spanned(
+          &assoc.ty,
+          "the items of a #[napi_stream] must be napi::Result<T>",
+        ));
+      }
+      return Ok(assoc.ty.clone());
+    }
+  }
+
+  Err(error(ty))
+}
+
+/// Converts an async function using `yield_!(value)` into a function returning a
+/// napi_ext::stream::Generator. The return type of the async function is the type it yields
+fn into_generator(func: &mut ItemFn) -> Result<Type, syn::Error> {
+  let item = match &func.sig.output {
+    ReturnType::Type(_, ty) => (**ty).clone(),
+    ReturnType::Default => {
+      return Err(syn::Error::new_spanned(
+        &func.sig,
+        "#[napi_stream] async functions must return the type they yield",
+      ));
+    }
+  };
+
+  if is_result(&item) {
+    return Err(syn::Error::new_spanned(
+      &item,
+      "#[napi_stream] async functions return the type they yield, use ? to end the stream with an error",
+    ));
+  }
+
+  let mut yields = YieldRewriter::default();
+  yields.visit_block_mut(&mut func.block);
+  if let Some(error) = yields.error {
+    return Err(error);
+  }
+
+  let block = func.block.clone();
+  func.sig.asyncness = None;
+  func.sig.output = syn::parse_quote! { -> napi_ext::stream::Generator<#item> };
+  func.block = syn::parse_quote! {{
+    napi_ext::stream::generator(move |napi_stream_yielder| async move {
+      #block;
+      napi::Result::Ok(())
+    })
+  }};
+  // The end of the stream is unreachable when the body loops forever
+  func
+    .attrs
+    .push(syn::parse_quote! { #[allow(unreachable_code)] });
+
+  Ok(syn::parse_quote! { napi::Result<#item> })
+}
+
+/// Replaces `yield_!(value)` with `napi_stream_yielder.send(value).await`
+#[derive(Default)]
+struct YieldRewriter {
+  error: Option<syn::Error>,
+}
+
+impl YieldRewriter {
+  fn rewrite(
+    &mut self,
+    mac: &syn::Macro,
+  ) -> Option<Expr> {
+    if !mac.path.is_ident("yield_") {
+      return None;
+    }
+    match mac.parse_body::<Expr>() {
+      Ok(value) => Some(syn::parse_quote! { napi_stream_yielder.send(#value).await }),
+      Err(error) => {
+        self.error.get_or_insert(error);
+        None
+      }
+    }
+  }
+}
+
+impl VisitMut for YieldRewriter {
+  fn visit_expr_mut(
+    &mut self,
+    expr: &mut Expr,
+  ) {
+    if let Expr::Macro(mac) = expr {
+      if let Some(rewritten) = self.rewrite(&mac.mac) {
+        *expr = rewritten;
+        return;
+      }
+    }
+    syn::visit_mut::visit_expr_mut(self, expr);
+  }
+
+  fn visit_stmt_mut(
+    &mut self,
+    stmt: &mut Stmt,
+  ) {
+    if let Stmt::Macro(mac) = stmt {
+      if let Some(rewritten) = self.rewrite(&mac.mac) {
+        *stmt = Stmt::Expr(rewritten, Some(Default::default()));
+        return;
+      }
+    }
+    syn::visit_mut::visit_stmt_mut(self, stmt);
+  }
+
+  // Nested functions cannot yield from the stream
+  fn visit_item_mut(
+    &mut self,
+    _item: &mut syn::Item,
+  ) {
+  }
+}
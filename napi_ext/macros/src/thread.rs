@@ -0,0 +1,118 @@
+use quote::quote;
+use quote::quote_spanned;
+use syn::spanned::Spanned;
+use syn::FnArg;
+use syn::ItemFn;
+use syn::Type;
+use syn::Visibility;
+
+use crate::convert_inputs;
+use crate::convert_output;
+use crate::docs;
+use crate::is_env;
+use crate::is_js_type;
+use crate::options::Options;
+use crate::promise_ts_type;
+use crate::rename_inner;
+use crate::type_ident;
+use crate::Inputs;
+
+/// Converts a function into an exported function that runs it on a new thread
+/// and returns a Promise of its output
+pub fn convert_thread(
+  options: Options,
+  mut func: ItemFn,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+  check_signature(&func)?;
+
+  let ts_return_type = promise_ts_type(&options, &func.sig);
+  let Inputs {
+    wrapper_inputs,
+    call_args,
+    napi_args,
+  } = convert_inputs(&options, &mut func.sig, ts_return_type)?;
+  let output = convert_output(&options, &func.sig.output);
+  let (ident, inner_ident) = rename_inner(&mut func.sig);
+  let vis = std::mem::replace(&mut func.vis, Visibility::Inherited);
+  let docs = docs(&func.attrs);
+
+  // Asserted separately so a parameter that is not Send is reported on its type
+  let assertions = wrapper_inputs.iter().filter_map(|input| match input {
+    FnArg::Typed(input) if !is_env(&input.ty) => {
+      let ty = &input.ty;
+      Some(quote_spanned! { ty.span() => napi_ext::assert_thread_arg::<#ty>(); })
+    }
+    _ => None,
+  });
+
+  Ok(quote! {
+    #func
+
+    #(#docs)*
+    #[napi_derive::napi(#napi_args)]
+    #vis fn #ident(#wrapper_inputs) -> napi::Result<napi::JsObject> {
+      #(#assertions)*
+      napi_ext::spawn_thread(&env, move || {
+        let output = #inner_ident(#(#call_args),*);
+        napi::Result::Ok(move |#[allow(unused_variables)] env: napi::Env| #output)
+      })
+    }
+  })
+}
+
+/// Checks the parts of a signature that cannot run on another thread
+fn check_signature(func: &ItemFn) -> Result<(), syn::Error> {
+  let sig = &func.sig;
+
+  if let Some(asyncness) = &sig.asyncness {
+    return Err(syn::Error::new_spanned(
+      asyncness,
+      "#[napi_thread] functions cannot be async, use #[napi_async] to run async functions",
+    ));
+  }
+
+  if !sig.generics.params.is_empty() {
+    return Err(syn::Error::new_spanned(
+      &sig.generics,
+      "#[napi_thread] functions cannot be generic",
+    ));
+  }
+
+  for input in sig.inputs.iter() {
+    let ty = match input {
+      FnArg::Receiver(receiver) => {
+        return Err(syn::Error::new_spanned(
+          receiver,
+          "#[napi_thread] can only be used on functions",
+        ));
+      }
+      FnArg::Typed(input) => &*input.ty,
+    };
+
+    if let Type::Reference(reference) = ty {
+      return Err(syn::Error::new_spanned(
+        reference,
+        "borrowed parameters cannot be sent to a #[napi_thread] function, use an owned type such as String or Vec<T>",
+      ));
+    }
+
+    if is_env(ty) {
+      return Err(syn::Error::new_spanned(
+        ty,
+        "Env can only be used on the JavaScript thread and cannot be a #[napi_thread] parameter",
+      ));
+    }
+
+    // JsRc is Send so it can be dropped on any thread, but like the other napi_ext
+    // wrappers it can only be used on the JavaScript thread
+    let is_owned = type_ident(ty).is_some_and(|ident| ident == "JsValueOwned");
+    if is_js_type(ty) && !is_owned {
+      return Err(syn::Error::new_spanned(
+        ty,
+        "JavaScript values cannot be sent to a #[napi_thread] function, use a Rust type, JsValueOwned or Serialized",
+      ));
+    }
+  }
+
+  Ok(())
+}
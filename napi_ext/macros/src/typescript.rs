@@ -0,0 +1,182 @@
+use napi_derive_backend::ty_to_ts_type;
+use syn::GenericArgument;
+use syn::Pat;
+use syn::PathArguments;
+use syn::Type;
+
+/// A parameter in the TypeScript signature of an exported function
+pub struct TsArg {
+  pub name: String,
+  pub ts_type: String,
+  pub optional: bool,
+}
+
+/// Formats the parameters for `ts_args_type`. Like napi-derive, only
+/// trailing optional parameters are marked with `?`
+pub fn ts_args(args: &[TsArg]) -> String {
+  let required = args
+    .iter()
+    .rposition(|arg| !arg.optional)
+    .map_or(0, |i| i + 1);
+
+  args
+    .iter()
+    .enumerate()
+    .map(|(i, arg)| {
+      let optional = if i >= required { "?" } else { "" };
+      format!("{}{}: {}", arg.name, optional, arg.ts_type)
+    })
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// The name napi-derive would give a parameter in TypeScript
+pub fn ts_arg_name(
+  pat: &Pat,
+  index: usize,
+) -> String {
+  let Pat::Ident(pat) = pat else {
+    return format!("arg{}", index);
+  };
+
+  let mut name = String::new();
+  for (i, part) in pat
+    .ident
+    .to_string()
+    .split('_')
+    .filter(|p| !p.is_empty())
+    .enumerate()
+  {
+    let mut chars = part.chars();
+    if let Some(first) = chars.next() {
+      if i == 0 {
+        name.push(first);
+      } else {
+        name.extend(first.to_uppercase());
+      }
+      name.push_str(chars.as_str());
+    }
+  }
+  name
+}
+
+/// Returns the TypeScript type of a Rust type and whether it is optional.
+///
+/// napi-derive works out TypeScript types from the tokens of a type, so it does not know
+/// about the `Js*` handles or the napi_ext wrappers. Those are handled here and everything
+/// else is left to napi-derive
+pub fn ts_type(
+  ty: &Type,
+  is_return: bool,
+) -> (String, bool) {
+  match ty {
+    Type::Tuple(tuple) if tuple.elems.is_empty() && is_return => return ("void".into(), false),
+    Type::Reference(reference) => return ts_type(&reference.elem, is_return),
+    Type::Paren(paren) => return ts_type(&paren.elem, is_return),
+    _ => {}
+  }
+
+  let Type::Path(path) = ty else {
+    return ty_to_ts_type(ty, is_return, false, false);
+  };
+  let Some(segment) = path.path.segments.last() else {
+    return ty_to_ts_type(ty, is_return, false, false);
+  };
+
+  let generic = match &segment.arguments {
+    PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+      GenericArgument::Type(ty) => Some(ty),
+      _ => None,
+    }),
+    _ => None,
+  };
+
+  let ident = segment.ident.to_string();
+  match (ident.as_str(), generic) {
+    ("JsRc" | "SerdeValue", Some(inner)) => ts_type(inner, is_return),
+    // Also matches paths such as anyhow::Result
+    ("Result", Some(inner)) if is_return => ts_type(inner, is_return),
+    ("Vec", Some(inner)) => (format!("Array<{}>", ts_type(inner, is_return).0), false),
+    ("Option", Some(inner)) => {
+      let inner = union_member(ts_type(inner, is_return).0);
+      if is_return {
+        (format!("{} | null", inner), true)
+      } else {
+        (format!("{} | undefined | null", inner), true)
+      }
+    }
+    ("JsCallback", _) => (callback_ts_type(&segment.arguments), false),
+    (ident, _) => match js_type(ident) {
+      Some(ts_type) => (ts_type.into(), false),
+      None => ty_to_ts_type(ty, is_return, false, false),
+    },
+  }
+}
+
+/// Writes `JsCallback<(A, B), R>` as `(arg0: A, arg1: B) => R | Promise<R>`
+fn callback_ts_type(arguments: &PathArguments) -> String {
+  let mut generics = match arguments {
+    PathArguments::AngleBracketed(args) => args
+      .args
+      .iter()
+      .filter_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+      })
+      .collect::<Vec<_>>(),
+    _ => vec![],
+  }
+  .into_iter();
+
+  let args = match generics.next() {
+    Some(Type::Tuple(tuple)) => tuple.elems.iter().collect::<Vec<_>>(),
+    Some(ty) => vec![ty],
+    None => vec![],
+  };
+  let args = args
+    .into_iter()
+    .enumerate()
+    .map(|(i, ty)| format!("arg{}: {}", i, ts_type(ty, false).0))
+    .collect::<Vec<_>>()
+    .join(", ");
+
+  // The return value defaults to JsUnknown. Promises are awaited by call_async
+  let ret = match generics.next() {
+    Some(ty) => {
+      let ret = union_member(ts_type(ty, false).0);
+      format!("{} | Promise<{}>", ret, ret)
+    }
+    None => "unknown".to_string(),
+  };
+
+  format!("({}) => {}", args, ret)
+}
+
+/// TypeScript types that napi-derive does not know about
+fn js_type(ident: &str) -> Option<&'static str> {
+  Some(match ident {
+    "JsFunction" => "(...args: any[]) => any",
+    "JsString" => "string",
+    "JsNumber" => "number",
+    "JsBoolean" => "boolean",
+    "JsBigInt" => "bigint",
+    "JsNull" => "null",
+    "JsUndefined" => "undefined",
+    "JsSymbol" => "symbol",
+    "JsExternal" => "object",
+    "JsTypedArray" => "ArrayBufferView",
+    "JsDataView" => "DataView",
+    "JsTimeout" => "unknown",
+    "JsValueOwned" | "Serialized" => "any",
+    _ => return None,
+  })
+}
+
+/// Function types need parentheses to be part of a union
+fn union_member(ts_type: String) -> String {
+  if ts_type.contains("=>") {
+    format!("({})", ts_type)
+  } else {
+    ts_type
+  }
+}
@@ -0,0 +1,106 @@
+use std::cell::Cell;
+use std::ptr;
+
+use napi::check_status;
+use napi::sys as napi_sys;
+use napi::Env;
+use napi::NapiValue;
+
+/// Runs the callback within a new handle scope. Handles created by the callback
+/// are released when it returns, even if it panics.
+///
+/// `R` should not be a napi handle such as [`napi::JsObject`] or [`napi::JsUnknown`] created
+/// within the scope, it is invalid once the scope closes. Return Rust values, or use an
+/// [`EscapableScope`] to keep one handle for the parent scope.
+pub fn with_scope<F, R>(
+  env: &Env,
+  callback: F,
+) -> napi::Result<R>
+where
+  F: FnOnce(&Env) -> napi::Result<R>,
+{
+  let _scope = HandleScope::open(env)?;
+  callback(env)
+}
+
+/// A handle scope that is closed when dropped, including while unwinding from a panic
+pub(crate) struct HandleScope {
+  env: Env,
+  raw_scope: napi_sys::napi_handle_scope,
+}
+
+impl HandleScope {
+  pub(crate) fn open(env: &Env) -> napi::Result<Self> {
+    let mut raw_scope = ptr::null_mut();
+    check_status!(unsafe { napi_sys::napi_open_handle_scope(env.raw(), &mut raw_scope) })?;
+
+    Ok(Self {
+      env: *env,
+      raw_scope,
+    })
+  }
+}
+
+impl Drop for HandleScope {
+  fn drop(&mut self) {
+    unsafe { napi_sys::napi_close_handle_scope(self.env.raw(), self.raw_scope) };
+  }
+}
+
+/// A handle scope that can promote one value to the parent scope before it is closed.
+/// The scope is closed when dropped.
+///
+/// ```ignore
+/// let mut total = env.create_uint32(0)?;
+/// for value in values {
+///   let scope = EscapableScope::open(&env)?;
+///   let sum = add.call(None, &[total, env.create_uint32(value)?])?;
+///   total = scope.escape(sum.coerce_to_number()?)?;
+/// }
+/// ```
+pub struct EscapableScope {
+  env: Env,
+  raw_scope: napi_sys::napi_escapable_handle_scope,
+  escaped: Cell<bool>,
+}
+
+impl EscapableScope {
+  pub fn open(env: &Env) -> napi::Result<Self> {
+    let mut raw_scope = ptr::null_mut();
+    check_status!(unsafe {
+      napi_sys::napi_open_escapable_handle_scope(env.raw(), &mut raw_scope)
+    })?;
+
+    Ok(Self {
+      env: *env,
+      raw_scope,
+      escaped: Cell::new(false),
+    })
+  }
+
+  /// Returns a handle to the value that remains valid in the parent scope.
+  /// Only one value can be escaped from a scope
+  pub fn escape<V: NapiValue>(
+    &self,
+    value: V,
+  ) -> napi::Result<V> {
+    if self.escaped.replace(true) {
+      return Err(napi::Error::from_reason(
+        "A value has already been escaped from this scope",
+      ));
+    }
+
+    let mut raw_value = ptr::null_mut();
+    check_status!(unsafe {
+      napi_sys::napi_escape_handle(self.env.raw(), self.raw_scope, value.raw(), &mut raw_value)
+    })?;
+
+    Ok(unsafe { V::from_raw_unchecked(self.env.raw(), raw_value) })
+  }
+}
+
+impl Drop for EscapableScope {
+  fn drop(&mut self) {
+    unsafe { napi_sys::napi_close_escapable_handle_scope(self.env.raw(), self.raw_scope) };
+  }
+}
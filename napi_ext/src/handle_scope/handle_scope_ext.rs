@@ -0,0 +1,62 @@
+use napi::Env;
+use napi::NapiValue;
+
+use super::with_scope;
+use super::EscapableScope;
+
+pub trait HandleScopeExt {
+  /// Runs the callback within a new handle scope, releasing the handles it creates
+  /// when it returns. Useful for loops that create many values within a single poll
+  /// of a future. Handles created in the scope must not be returned from it, use
+  /// [`HandleScopeExt::with_escapable_scope`] to return one.
+  ///
+  /// ```ignore
+  /// while let Ok(value) = rx.recv().await {
+  ///   env.with_scope(|env| {
+  ///     callback.call(None, &[env.create_int32(value)?])?;
+  ///     Ok(())
+  ///   })?;
+  /// }
+  /// ```
+  fn with_scope<F, R>(
+    &self,
+    callback: F,
+  ) -> napi::Result<R>
+  where
+    F: FnOnce(&Env) -> napi::Result<R>;
+
+  /// Runs the callback within a new handle scope and returns the value it
+  /// returns to the parent scope
+  fn with_escapable_scope<F, V>(
+    &self,
+    callback: F,
+  ) -> napi::Result<V>
+  where
+    F: FnOnce(&Env) -> napi::Result<V>,
+    V: NapiValue;
+}
+
+impl HandleScopeExt for Env {
+  fn with_scope<F, R>(
+    &self,
+    callback: F,
+  ) -> napi::Result<R>
+  where
+    F: FnOnce(&Env) -> napi::Result<R>,
+  {
+    with_scope(self, callback)
+  }
+
+  fn with_escapable_scope<F, V>(
+    &self,
+    callback: F,
+  ) -> napi::Result<V>
+  where
+    F: FnOnce(&Env) -> napi::Result<V>,
+    V: NapiValue,
+  {
+    let scope = EscapableScope::open(self)?;
+    let value = callback(self)?;
+    scope.escape(value)
+  }
+}
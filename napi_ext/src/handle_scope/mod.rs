@@ -0,0 +1,5 @@
+mod handle_scope;
+mod handle_scope_ext;
+
+pub use self::handle_scope::*;
+pub use self::handle_scope_ext::*;
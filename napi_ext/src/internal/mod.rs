@@ -3,6 +3,11 @@ use std::ffi::c_void;
 use std::ptr;
 
 use napi::sys as napi_sys;
+use napi::Env;
+use napi::JsFunction;
+use napi::JsObject;
+use napi::JsUnknown;
+use napi::ValueType;
 
 pub fn declare_threadsafe_function(
   env: napi_sys::napi_env,
@@ -47,3 +52,61 @@ pub fn declare_threadsafe_function(
     raw_tsfn
   }
 }
+
+/// Gets the well-known `Symbol.asyncIterator` symbol
+pub fn symbol_async_iterator(env: &Env) -> napi::Result<JsUnknown> {
+  env
+    .get_global()?
+    .get_named_property_unchecked::<JsObject>("Symbol")?
+    .get_named_property("asyncIterator")
+}
+
+/// Loads a Node.js builtin module (e.g. `stream`) using `process.getBuiltinModule`,
+/// falling back to a global `require` when it is not available
+pub fn require_builtin(
+  env: &Env,
+  name: &str,
+) -> napi::Result<JsObject> {
+  let global = env.get_global()?;
+  let process = global.get_named_property::<JsObject>("process")?;
+
+  let get_builtin_module = process.get_named_property::<JsUnknown>("getBuiltinModule")?;
+  let module = if get_builtin_module.get_type()? == ValueType::Function {
+    JsFunction::try_from(get_builtin_module)?.call(Some(&process), &[env.create_string(name)?])?
+  } else {
+    let require = global.get_named_property::<JsUnknown>("require")?;
+    if require.get_type()? != ValueType::Function {
+      return Err(napi::Error::from_reason(format!(
+        "Unable to load \"{}\", process.getBuiltinModule is not available",
+        name
+      )));
+    }
+    JsFunction::try_from(require)?.call(None, &[env.create_string(name)?])?
+  };
+
+  // Some modules (like "stream") are functions
+  module.coerce_to_object()
+}
+
+/// Calls `object[method](...args)`
+pub fn call_method(
+  object: &JsObject,
+  method: &str,
+  args: &[JsUnknown],
+) -> napi::Result<JsUnknown> {
+  object
+    .get_named_property::<JsFunction>(method)?
+    .call(Some(object), args)
+}
+
+/// Gets a WHATWG stream constructor from the global scope, falling back to `stream/web`
+pub fn web_stream_ctor(
+  env: &Env,
+  name: &str,
+) -> napi::Result<JsFunction> {
+  let ctor = env.get_global()?.get_named_property::<JsUnknown>(name)?;
+  if ctor.get_type()? == ValueType::Function {
+    return JsFunction::try_from(ctor);
+  }
+  require_builtin(env, "stream/web")?.get_named_property(name)
+}
@@ -0,0 +1,180 @@
+use std::ffi::c_void;
+use std::fmt;
+use std::ptr;
+
+use napi::bindgen_prelude::ToNapiValue;
+use napi::check_status;
+use napi::sys as napi_sys;
+use napi::Env;
+use napi::JsObject;
+use napi::JsUnknown;
+use napi::NapiRaw;
+use napi::NapiValue;
+use napi::ValueType;
+
+use crate::JsRc;
+
+const SYM_EXCEPTION_VALUE: &str = "value";
+
+// Address used to tag the objects that box primitive exceptions
+static EXCEPTION_BOX_TAG: u8 = 0;
+
+/// A value that can be thrown into JavaScript or used to reject a Promise.
+///
+/// Holds either a [`napi::Error`] or an arbitrary JavaScript value. JavaScript
+/// values (like an exception thrown by a callback) are rethrown unchanged so
+/// their identity and stack trace are preserved.
+pub enum JsException {
+  Error(napi::Error),
+  Value(JsRc<JsUnknown>),
+}
+
+impl JsException {
+  /// Wraps any JavaScript value so it can be thrown as-is
+  pub fn from_value<V: NapiRaw>(
+    env: &Env,
+    value: V,
+  ) -> napi::Result<Self> {
+    let value = unsafe { JsUnknown::from_raw_unchecked(env.raw(), value.raw()) };
+    Ok(Self::Value(JsRc::new(env, value)?))
+  }
+
+  /// Takes the pending JavaScript exception, if there is one, and clears it
+  /// from the [`Env`] using `napi_get_and_clear_last_exception`
+  pub fn take_pending(env: &Env) -> napi::Result<Option<Self>> {
+    let mut is_pending = false;
+    check_status!(unsafe { napi_sys::napi_is_exception_pending(env.raw(), &mut is_pending) })?;
+    if !is_pending {
+      return Ok(None);
+    }
+
+    let mut raw_value = ptr::null_mut();
+    check_status!(unsafe {
+      napi_sys::napi_get_and_clear_last_exception(env.raw(), &mut raw_value)
+    })?;
+
+    let value = unsafe { JsUnknown::from_raw_unchecked(env.raw(), raw_value) };
+    Ok(Some(Self::Value(JsRc::new(env, value)?)))
+  }
+
+  /// Converts the exception into the JavaScript value that will be thrown.
+  ///
+  /// If a JavaScript exception is pending (for instance a [`napi::Error`]
+  /// propagated with `?` after a callback threw), the original exception
+  /// is used rather than a new `Error` built from the message.
+  pub fn into_unknown(
+    self,
+    env: &Env,
+  ) -> napi::Result<JsUnknown> {
+    // Always clear the pending exception, napi calls fail while one is pending
+    let pending = Self::take_pending(env)?;
+
+    match (self, pending) {
+      (Self::Value(value), _) => value.get(),
+      (Self::Error(_), Some(Self::Value(value))) => value.get(),
+      (Self::Error(error), _) => {
+        let raw_value = unsafe { napi::Error::to_napi_value(env.raw(), error)? };
+        if let Some(value) = unbox_primitive(env, raw_value)? {
+          return Ok(value);
+        }
+        Ok(unsafe { JsUnknown::from_raw_unchecked(env.raw(), raw_value) })
+      }
+    }
+  }
+}
+
+/// [`napi::Error`] can only reference objects, so primitive values are boxed
+/// in a tagged object which is unwrapped again when the exception is thrown
+fn box_primitive(
+  env: &Env,
+  value: JsUnknown,
+) -> napi::Result<JsUnknown> {
+  if matches!(
+    value.get_type()?,
+    ValueType::Object | ValueType::Function | ValueType::External
+  ) {
+    return Ok(value);
+  }
+
+  let mut boxed = env.create_object()?;
+  boxed.set_named_property(SYM_EXCEPTION_VALUE, value)?;
+
+  let tag = ptr::addr_of!(EXCEPTION_BOX_TAG) as *mut c_void;
+  check_status!(unsafe {
+    napi_sys::napi_wrap(
+      env.raw(),
+      boxed.raw(),
+      tag,
+      None,
+      ptr::null_mut(),
+      ptr::null_mut(),
+    )
+  })?;
+
+  Ok(boxed.into_unknown())
+}
+
+fn unbox_primitive(
+  env: &Env,
+  raw_value: napi_sys::napi_value,
+) -> napi::Result<Option<JsUnknown>> {
+  let mut tag = ptr::null_mut();
+  let status = unsafe { napi_sys::napi_unwrap(env.raw(), raw_value, &mut tag) };
+  if status != napi_sys::Status::napi_ok || tag != ptr::addr_of!(EXCEPTION_BOX_TAG) as *mut c_void {
+    return Ok(None);
+  }
+
+  let boxed = unsafe { JsObject::from_raw_unchecked(env.raw(), raw_value) };
+  Ok(Some(boxed.get_named_property(SYM_EXCEPTION_VALUE)?))
+}
+
+impl From<napi::Error> for JsException {
+  fn from(error: napi::Error) -> Self {
+    Self::Error(error)
+  }
+}
+
+impl From<JsRc<JsUnknown>> for JsException {
+  fn from(value: JsRc<JsUnknown>) -> Self {
+    Self::Value(value)
+  }
+}
+
+impl From<JsException> for napi::Error {
+  fn from(exception: JsException) -> Self {
+    match exception {
+      JsException::Error(error) => error,
+      JsException::Value(value) => match value
+        .get()
+        .and_then(|inner| box_primitive(&value.env(), inner))
+      {
+        Ok(value) => napi::Error::from(value),
+        Err(error) => error,
+      },
+    }
+  }
+}
+
+impl fmt::Debug for JsException {
+  fn fmt(
+    &self,
+    f: &mut fmt::Formatter<'_>,
+  ) -> fmt::Result {
+    match self {
+      Self::Error(error) => f.debug_tuple("Error").field(error).finish(),
+      Self::Value(_) => f.debug_tuple("Value").finish(),
+    }
+  }
+}
+
+impl fmt::Display for JsException {
+  fn fmt(
+    &self,
+    f: &mut fmt::Formatter<'_>,
+  ) -> fmt::Result {
+    match self {
+      Self::Error(error) => write!(f, "{}", error),
+      Self::Value(_) => write!(f, "JavaScript exception"),
+    }
+  }
+}
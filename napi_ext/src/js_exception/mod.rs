@@ -0,0 +1,3 @@
+mod js_exception;
+
+pub use self::js_exception::*;
@@ -0,0 +1,181 @@
+use std::any::type_name;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::ops::DerefMut;
+use std::ptr;
+
+use napi::check_status;
+use napi::sys as napi_sys;
+use napi::Env;
+use napi::JsObject;
+use napi::NapiRaw;
+
+use crate::JsRc;
+
+thread_local! {
+  /// Borrow state of class instances held by a JsInstance, keyed by the wrapped value.
+  /// Positive counts are shared borrows and -1 is an exclusive borrow
+  static BORROWS: RefCell<HashMap<usize, isize>> = RefCell::new(HashMap::new());
+}
+
+/// A handle to the Rust value of a `#[napi]` class instance that keeps the
+/// JavaScript object alive. This is how `#[napi_async]` methods hold `self` across awaits.
+///
+/// Borrows are checked at runtime, like a [`std::cell::RefCell`], across all
+/// `JsInstance`s of the same object. Synchronous `#[napi]` methods are not checked,
+/// see [`JsInstance::new`] for what they must not do while a borrow is held.
+///
+/// ```ignore
+/// #[napi]
+/// impl Client {
+///   #[napi]
+///   pub fn refresh(&self, env: Env, this: This) -> napi::Result<JsObject> {
+///     // Safety: Client has no synchronous &mut self methods
+///     let client = unsafe { JsInstance::new(&env, this, self) }?.borrow_mut()?;
+///     env.spawn_local_promise(async move { client.refresh().await })
+///   }
+/// }
+/// ```
+pub struct JsInstance<T: 'static> {
+  this: JsRc<JsObject>,
+  raw: *mut T,
+}
+
+impl<T: 'static> JsInstance<T> {
+  /// `this` is the JavaScript object of a class method and `instance` is its `&self`
+  ///
+  /// # Safety
+  ///
+  /// Borrows taken from the `JsInstance` are only checked against other `JsInstance`s.
+  /// While one is held, which can be across awaits, the class must not be used through
+  /// unchecked references. Synchronous `#[napi]` methods taking `&mut self` must not be
+  /// called, and neither may `&self` methods while an exclusive borrow is held.
+  pub unsafe fn new(
+    env: &Env,
+    this: JsObject,
+    instance: &T,
+  ) -> napi::Result<Self> {
+    let mut raw = ptr::null_mut();
+    check_status!(
+      unsafe { napi_sys::napi_unwrap(env.raw(), this.raw(), &mut raw) },
+      "Failed to unwrap {} from this",
+      type_name::<T>()
+    )?;
+
+    if !ptr::eq(raw.cast::<T>(), instance) {
+      return Err(napi::Error::from_reason(format!(
+        "this is not the instance of {}",
+        type_name::<T>()
+      )));
+    }
+
+    Ok(Self {
+      this: JsRc::new(env, this)?,
+      raw: raw.cast(),
+    })
+  }
+
+  /// The JavaScript object of the class instance
+  pub fn this(&self) -> &JsRc<JsObject> {
+    &self.this
+  }
+
+  /// Takes a shared borrow of the instance, failing if it is exclusively borrowed
+  pub fn borrow(self) -> napi::Result<JsInstanceRef<T>> {
+    self.update_borrows(|count| (count >= 0).then_some(count + 1))?;
+    Ok(JsInstanceRef { instance: self })
+  }
+
+  /// Takes an exclusive borrow of the instance, failing if it is already borrowed
+  pub fn borrow_mut(self) -> napi::Result<JsInstanceRefMut<T>> {
+    self.update_borrows(|count| (count == 0).then_some(-1))?;
+    Ok(JsInstanceRefMut { instance: self })
+  }
+
+  fn update_borrows(
+    &self,
+    update: impl FnOnce(isize) -> Option<isize>,
+  ) -> napi::Result<()> {
+    BORROWS.with(|borrows| {
+      let mut borrows = borrows.borrow_mut();
+      let count = borrows.get(&self.key()).copied().unwrap_or(0);
+      let Some(count) = update(count) else {
+        return Err(napi::Error::from_reason(format!(
+          "{} is already borrowed by another call",
+          type_name::<T>()
+        )));
+      };
+
+      if count == 0 {
+        borrows.remove(&self.key());
+      } else {
+        borrows.insert(self.key(), count);
+      }
+      Ok(())
+    })
+  }
+
+  fn key(&self) -> usize {
+    self.raw as usize
+  }
+}
+
+/// A shared borrow of a class instance, returned from [`JsInstance::borrow`]
+pub struct JsInstanceRef<T: 'static> {
+  instance: JsInstance<T>,
+}
+
+impl<T: 'static> JsInstanceRef<T> {
+  pub fn this(&self) -> &JsRc<JsObject> {
+    self.instance.this()
+  }
+}
+
+impl<T: 'static> Deref for JsInstanceRef<T> {
+  type Target = T;
+
+  fn deref(&self) -> &Self::Target {
+    // Safety: The JsRc keeps the object and its wrapped value alive
+    unsafe { &*self.instance.raw }
+  }
+}
+
+impl<T: 'static> Drop for JsInstanceRef<T> {
+  fn drop(&mut self) {
+    self.instance.update_borrows(|count| Some(count - 1)).ok();
+  }
+}
+
+/// An exclusive borrow of a class instance, returned from [`JsInstance::borrow_mut`]
+pub struct JsInstanceRefMut<T: 'static> {
+  instance: JsInstance<T>,
+}
+
+impl<T: 'static> JsInstanceRefMut<T> {
+  pub fn this(&self) -> &JsRc<JsObject> {
+    self.instance.this()
+  }
+}
+
+impl<T: 'static> Deref for JsInstanceRefMut<T> {
+  type Target = T;
+
+  fn deref(&self) -> &Self::Target {
+    // Safety: The JsRc keeps the object and its wrapped value alive
+    unsafe { &*self.instance.raw }
+  }
+}
+
+impl<T: 'static> DerefMut for JsInstanceRefMut<T> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    // Safety: The borrow state guarantees no other JsInstance borrows the value
+    unsafe { &mut *self.instance.raw }
+  }
+}
+
+impl<T: 'static> Drop for JsInstanceRefMut<T> {
+  fn drop(&mut self) {
+    self.instance.update_borrows(|_| Some(0)).ok();
+  }
+}
@@ -0,0 +1,3 @@
+mod js_instance;
+
+pub use self::js_instance::*;
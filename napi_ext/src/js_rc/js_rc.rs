@@ -1,26 +1,49 @@
 #![allow(dead_code)]
-use std::cell::OnceCell;
+use std::any::type_name;
 use std::marker::PhantomData;
-use std::ops::Deref;
 use std::ptr;
+use std::sync::Arc;
+use std::thread;
+use std::thread::ThreadId;
 
+use napi::bindgen_prelude::FromNapiValue;
+use napi::bindgen_prelude::TypeName;
 use napi::check_status;
 use napi::sys as napi_sys;
 use napi::Env;
 use napi::NapiRaw;
 use napi::NapiValue;
+use napi::ValueType;
 
-thread_local! {
-  static NAPI_ENV: OnceCell<*mut napi_sys::napi_env__> = OnceCell::default();
-}
+use crate::runtime::RuntimeHandle;
 
 unsafe impl<T> Send for JsRc<T> {}
 
+/// A reference counted handle to a JavaScript value that prevents Nodejs's GC
+/// from dropping it.
+///
+/// A `JsRc` can be sent to other threads but the value can only be accessed on the
+/// JavaScript thread that created it. Dropping the last clone on another thread defers
+/// releasing the value to the JavaScript thread.
 pub struct JsRc<T> {
-  raw_ref: napi_sys::napi_ref,
+  reference: Arc<JsReference>,
   _inner: PhantomData<T>,
 }
 
+/// The napi reference shared by all clones of a JsRc
+struct JsReference {
+  raw_env: napi_sys::napi_env,
+  raw_ref: napi_sys::napi_ref,
+  // Primitives cannot be referenced directly so they are stored in a one element array
+  wrapped: bool,
+  thread_id: ThreadId,
+  runtime: RuntimeHandle,
+}
+
+// Safety: The napi reference is only used on the thread that created it
+unsafe impl Send for JsReference {}
+unsafe impl Sync for JsReference {}
+
 impl<T: NapiValue> JsRc<T> {
   pub fn new(
     env: &Env,
@@ -29,98 +52,207 @@ impl<T: NapiValue> JsRc<T> {
     Self::new_raw(env.raw(), unsafe { inner.raw() })
   }
 
-  fn new_raw(
+  pub(crate) fn new_raw(
     raw_env: napi_sys::napi_env,
     inner_raw: napi_sys::napi_value,
   ) -> napi::Result<Self> {
-    let raw_env = NAPI_ENV.with(|f| *f.get_or_init(|| raw_env));
+    let mut value_type = napi_sys::ValueType::napi_undefined;
+    check_status!(unsafe { napi_sys::napi_typeof(raw_env, inner_raw, &mut value_type) })?;
 
-    let obj = {
+    let wrapped = !matches!(
+      value_type,
+      napi_sys::ValueType::napi_object
+        | napi_sys::ValueType::napi_function
+        | napi_sys::ValueType::napi_external
+    );
+
+    let obj = if wrapped {
       let mut raw_value = ptr::null_mut();
       check_status!(unsafe {
         napi_sys::napi_create_array_with_length(raw_env, 1, &mut raw_value)
       })?;
+      check_status!(unsafe { napi_sys::napi_set_element(raw_env, raw_value, 0, inner_raw) })?;
       raw_value
+    } else {
+      inner_raw
     };
 
-    check_status!(unsafe { napi_sys::napi_set_element(raw_env, obj, 0, inner_raw.cast()) })?;
-
     let mut raw_ref = ptr::null_mut();
     check_status!(unsafe { napi_sys::napi_create_reference(raw_env, obj, 1, &mut raw_ref) })?;
 
+    let env = unsafe { Env::from_raw(raw_env) };
     Ok(Self {
-      raw_ref,
+      reference: Arc::new(JsReference {
+        raw_env,
+        raw_ref,
+        wrapped,
+        thread_id: thread::current().id(),
+        runtime: RuntimeHandle::current(&env),
+      }),
       _inner: Default::default(),
     })
   }
 
+  /// Gets the JavaScript value. Returns an error when called from a thread other
+  /// than the JavaScript thread that created it
   pub fn get(&self) -> napi::Result<T> {
-    let env_raw = NAPI_ENV.with(|f| *f.get().unwrap());
+    let value = self.get_raw()?;
+    Ok(unsafe { T::from_raw_unchecked(self.reference.raw_env, value) })
+  }
 
-    let mut napi_value = ptr::null_mut();
-    unsafe { napi_sys::napi_get_reference_value(env_raw, self.raw_ref, &mut napi_value) };
+  /// Gets the JavaScript value, returning `None` when called from
+  /// another thread or if it could not be read
+  pub fn try_get(&self) -> Option<T> {
+    self.get().ok()
+  }
 
-    let mut raw_value = ptr::null_mut();
-    unsafe { napi_sys::napi_get_element(env_raw, napi_value, 0, &mut raw_value) };
+  /// Gets the JavaScript value, checking that `env` created it
+  pub fn inner(
+    &self,
+    env: &Env,
+  ) -> napi::Result<T> {
+    self.check_env(env)?;
+    self.get()
+  }
+
+  /// Gets the JavaScript value and releases this reference to it
+  pub fn into_inner(
+    self,
+    env: &Env,
+  ) -> napi::Result<T> {
+    self.inner(env)
+  }
 
-    let value = unsafe { T::from_raw_unchecked(env_raw, raw_value) };
+  /// Converts to a reference of another JavaScript type, checking the type
+  /// of the value with `napi_typeof`. Both share the same underlying reference
+  ///
+  /// ```ignore
+  /// let callback: JsRc<JsFunction> = value.cast()?;
+  /// ```
+  pub fn cast<U: NapiValue>(&self) -> napi::Result<JsRc<U>> {
+    let value = self.get_raw()?;
+    unsafe { U::from_raw(self.reference.raw_env, value) }?;
 
-    Ok(value)
+    Ok(JsRc {
+      reference: self.reference.clone(),
+      _inner: PhantomData,
+    })
   }
 }
 
-impl<T> Clone for JsRc<T> {
-  fn clone(&self) -> Self {
-    let env_raw = NAPI_ENV.with(|f| *f.get().unwrap());
+impl<T> JsRc<T> {
+  pub(crate) fn env(&self) -> Env {
+    unsafe { Env::from_raw(self.reference.raw_env) }
+  }
 
-    unsafe { napi_sys::napi_reference_ref(env_raw, self.raw_ref.cast(), ptr::null_mut()) };
+  fn get_raw(&self) -> napi::Result<napi_sys::napi_value> {
+    self.check_thread()?;
+    let JsReference {
+      raw_env,
+      raw_ref,
+      wrapped,
+      ..
+    } = *self.reference;
 
-    Self {
-      raw_ref: self.raw_ref.clone(),
-      _inner: self._inner.clone(),
+    let mut napi_value = ptr::null_mut();
+    check_status!(unsafe {
+      napi_sys::napi_get_reference_value(raw_env, raw_ref, &mut napi_value)
+    })?;
+
+    if wrapped {
+      let mut raw_value = ptr::null_mut();
+      check_status!(unsafe { napi_sys::napi_get_element(raw_env, napi_value, 0, &mut raw_value) })?;
+      napi_value = raw_value;
+    }
+
+    Ok(napi_value)
+  }
+
+  /// Returns an error when called from a thread other than the JavaScript thread
+  /// that created the value
+  fn check_thread(&self) -> napi::Result<()> {
+    if self.reference.thread_id == thread::current().id() {
+      return Ok(());
     }
+
+    Err(napi::Error::from_reason(format!(
+      "{} was accessed from {:?} but can only be used on the JavaScript thread that created it ({:?})",
+      type_name::<Self>(),
+      thread::current().id(),
+      self.reference.thread_id,
+    )))
+  }
+
+  /// Returns an error if `env` is not the environment that created the value
+  pub(crate) fn check_env(
+    &self,
+    env: &Env,
+  ) -> napi::Result<()> {
+    if env.raw() == self.reference.raw_env {
+      return Ok(());
+    }
+
+    Err(napi::Error::from_reason(format!(
+      "{} was used with an Env that did not create it",
+      type_name::<Self>(),
+    )))
   }
 }
 
-impl<T> Drop for JsRc<T> {
-  fn drop(&mut self) {
-    let env_raw = NAPI_ENV.with(|f| *f.get().unwrap());
-    let mut count = 0;
-    unsafe { napi_sys::napi_reference_unref(env_raw, self.raw_ref.cast(), &mut count) };
-    if count == 0 {
-      unsafe { napi_sys::napi_delete_reference(env_raw, self.raw_ref.cast()) };
+impl<T> Clone for JsRc<T> {
+  fn clone(&self) -> Self {
+    Self {
+      reference: self.reference.clone(),
+      _inner: self._inner,
     }
   }
 }
 
-impl<T: NapiValue> Deref for JsRc<T> {
-  type Target = T;
+impl Drop for JsReference {
+  fn drop(&mut self) {
+    if self.thread_id == thread::current().id() {
+      unsafe { napi_sys::napi_delete_reference(self.raw_env, self.raw_ref) };
+      return;
+    }
 
-  fn deref(&self) -> &Self::Target {
-    let value = self.get().unwrap();
-    let value = Box::leak(Box::new(value));
-    value
+    // napi references can only be released on the JavaScript thread
+    let raw_env = self.raw_env as usize;
+    let raw_ref = self.raw_ref as usize;
+    self.runtime.run_on_js_thread(move || unsafe {
+      napi_sys::napi_delete_reference(raw_env as napi_sys::napi_env, raw_ref as napi_sys::napi_ref);
+    });
   }
 }
 
+/// [`NapiRaw`] cannot return an error, so the value must only be passed to napi on the
+/// JavaScript thread that created it. Otherwise this panics with the error returned by
+/// [`JsRc::get`], which should be used where the thread is not known.
 impl<T: NapiValue> NapiRaw for JsRc<T> {
   unsafe fn raw(&self) -> napi_sys::napi_value {
-    self.get().unwrap().raw()
+    match self.get_raw() {
+      Ok(raw_value) => raw_value,
+      Err(error) => panic!("{}", error.reason),
+    }
   }
 }
 
-impl<T: NapiValue> NapiValue for JsRc<T> {
-  unsafe fn from_raw(
+impl<T: NapiValue> FromNapiValue for JsRc<T> {
+  unsafe fn from_napi_value(
     env: napi_sys::napi_env,
-    value: napi_sys::napi_value,
+    napi_val: napi_sys::napi_value,
   ) -> napi::Result<Self> {
-    JsRc::new_raw(env, value)
+    // Checks the type of the value
+    T::from_raw(env, napi_val)?;
+    JsRc::new_raw(env, napi_val)
   }
+}
 
-  unsafe fn from_raw_unchecked(
-    env: napi_sys::napi_env,
-    value: napi_sys::napi_value,
-  ) -> Self {
-    JsRc::new_raw(env, value).unwrap()
+impl<T: TypeName> TypeName for JsRc<T> {
+  fn type_name() -> &'static str {
+    T::type_name()
+  }
+
+  fn value_type() -> ValueType {
+    T::value_type()
   }
 }
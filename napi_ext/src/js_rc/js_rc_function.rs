@@ -0,0 +1,89 @@
+use std::future::Future;
+
+use futures::future::LocalBoxFuture;
+use futures::FutureExt;
+use napi::bindgen_prelude::FromNapiValue;
+use napi::bindgen_prelude::JsValuesTupleIntoVec;
+use napi::JsFunction;
+use napi::JsObject;
+use napi::JsUnknown;
+use napi::NapiRaw;
+use napi::NapiValue;
+use napi::ValueType;
+
+use super::JsRc;
+use crate::JsPromise;
+
+impl JsRc<JsFunction> {
+  /// Calls the function and awaits its result on the local runtime.
+  ///
+  /// If the function returns a thenable (such as the Promise returned by an `async`
+  /// function) it is awaited, otherwise the returned value resolves immediately.
+  /// `args` is a tuple of [`napi::bindgen_prelude::ToNapiValue`] values.
+  ///
+  /// ```ignore
+  /// #[napi]
+  /// fn run_hook(env: Env, hook: JsRc<JsFunction>) -> napi::Result<JsObject> {
+  ///   env.spawn_local_promise(async move {
+  ///     let count: u32 = hook.call_async(None, ("build", 42)).await?;
+  ///     Ok(count + 1)
+  ///   })
+  /// }
+  /// ```
+  pub fn call_async<Args, R>(
+    &self,
+    this: Option<&JsObject>,
+    args: Args,
+  ) -> impl Future<Output = napi::Result<R>>
+  where
+    Args: JsValuesTupleIntoVec,
+    R: FromNapiValue,
+  {
+    let env = self.env();
+    let result = self.call_thenable(this, args);
+
+    async move {
+      let value = result?.await?;
+      unsafe { R::from_napi_value(env.raw(), value.raw()) }
+    }
+  }
+
+  fn call_thenable<Args: JsValuesTupleIntoVec>(
+    &self,
+    this: Option<&JsObject>,
+    args: Args,
+  ) -> napi::Result<LocalBoxFuture<'static, napi::Result<JsRc<JsUnknown>>>> {
+    let env = self.env();
+    let args = args
+      .into_vec(env.raw())?
+      .into_iter()
+      .map(|arg| unsafe { JsUnknown::from_raw_unchecked(env.raw(), arg) })
+      .collect::<Vec<_>>();
+
+    let value = self.get()?.call(this, &args)?;
+    if !is_thenable(&value)? {
+      let value = JsRc::new(&env, value)?;
+      return Ok(futures::future::ready(Ok(value)).boxed_local());
+    }
+
+    // Promise.resolve() adopts the state of thenables that are not native Promises
+    let promise_ctor = env
+      .get_global()?
+      .get_named_property_unchecked::<JsObject>("Promise")?;
+    let promise = promise_ctor
+      .get_named_property::<JsFunction>("resolve")?
+      .call(Some(&promise_ctor), &[value])?;
+
+    let future = JsPromise::from_object(&env, JsObject::try_from(promise)?)?.into_future()?;
+    Ok(future.boxed_local())
+  }
+}
+
+fn is_thenable(value: &JsUnknown) -> napi::Result<bool> {
+  if !matches!(value.get_type()?, ValueType::Object | ValueType::Function) {
+    return Ok(false);
+  }
+
+  let then = unsafe { value.cast::<JsObject>() }.get_named_property::<JsUnknown>("then")?;
+  Ok(then.get_type()? == ValueType::Function)
+}
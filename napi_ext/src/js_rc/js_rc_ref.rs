@@ -0,0 +1,46 @@
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use napi::Env;
+use napi::NapiValue;
+
+use super::JsRc;
+
+/// A JavaScript value borrowed from a [`JsRc`], returned from [`JsRc::borrow`].
+///
+/// The guard cannot outlive the `JsRc` it was borrowed from, which keeps the value alive
+pub struct JsRcRef<'a, T> {
+  value: T,
+  _rc: PhantomData<&'a JsRc<T>>,
+}
+
+impl<T: NapiValue> JsRc<T> {
+  /// Borrows the JavaScript value for the lifetime of the returned guard.
+  /// `env` must be the environment of the JavaScript thread that created the value
+  ///
+  /// ```ignore
+  /// let callback = callback.borrow(&env)?;
+  /// for i in 0..10 {
+  ///   callback.call(None, &[env.create_uint32(i)?])?;
+  /// }
+  /// ```
+  pub fn borrow<'a>(
+    &'a self,
+    env: &Env,
+  ) -> napi::Result<JsRcRef<'a, T>> {
+    self.check_env(env)?;
+
+    Ok(JsRcRef {
+      value: self.get()?,
+      _rc: PhantomData,
+    })
+  }
+}
+
+impl<T> Deref for JsRcRef<'_, T> {
+  type Target = T;
+
+  fn deref(&self) -> &Self::Target {
+    &self.value
+  }
+}
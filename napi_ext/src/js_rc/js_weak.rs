@@ -0,0 +1,65 @@
+use std::marker::PhantomData;
+use std::ptr;
+
+use napi::check_status;
+use napi::sys as napi_sys;
+use napi::NapiValue;
+
+use super::JsRc;
+
+/// A weak reference to a JavaScript object or function that does not prevent
+/// Nodejs's GC from collecting it. Created with [`JsRc::downgrade`].
+pub struct JsWeak<T> {
+  raw_env: napi_sys::napi_env,
+  raw_ref: napi_sys::napi_ref,
+  _inner: PhantomData<T>,
+}
+
+impl<T: NapiValue> JsRc<T> {
+  /// Creates a weak reference to the value. Only objects and functions can be weakly referenced
+  pub fn downgrade(&self) -> napi::Result<JsWeak<T>> {
+    let raw_env = self.env().raw();
+    let value = self.get()?;
+
+    let mut raw_ref = ptr::null_mut();
+    check_status!(
+      unsafe { napi_sys::napi_create_reference(raw_env, value.raw(), 0, &mut raw_ref) },
+      "Only objects and functions can be weakly referenced"
+    )?;
+
+    Ok(JsWeak {
+      raw_env,
+      raw_ref,
+      _inner: PhantomData,
+    })
+  }
+}
+
+impl<T: NapiValue> JsWeak<T> {
+  /// Returns a strong reference to the value if it has not been garbage collected
+  pub fn upgrade(&self) -> napi::Result<Option<JsRc<T>>> {
+    match self.get_raw()? {
+      Some(value) => Ok(Some(JsRc::new_raw(self.raw_env, value)?)),
+      None => Ok(None),
+    }
+  }
+
+  /// Returns `true` if the value has not been garbage collected
+  pub fn is_alive(&self) -> bool {
+    matches!(self.get_raw(), Ok(Some(_)))
+  }
+
+  fn get_raw(&self) -> napi::Result<Option<napi_sys::napi_value>> {
+    let mut value = ptr::null_mut();
+    check_status!(unsafe {
+      napi_sys::napi_get_reference_value(self.raw_env, self.raw_ref, &mut value)
+    })?;
+    Ok((!value.is_null()).then_some(value))
+  }
+}
+
+impl<T> Drop for JsWeak<T> {
+  fn drop(&mut self) {
+    unsafe { napi_sys::napi_delete_reference(self.raw_env, self.raw_ref) };
+  }
+}
@@ -1,5 +1,10 @@
 mod js_rc;
 mod js_rc_ext;
+mod js_rc_function;
+mod js_rc_ref;
+mod js_weak;
 
 pub use self::js_rc::*;
 pub use self::js_rc_ext::*;
+pub use self::js_rc_ref::*;
+pub use self::js_weak::*;
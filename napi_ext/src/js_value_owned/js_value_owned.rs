@@ -0,0 +1,312 @@
+use napi::bindgen_prelude::FromNapiValue;
+use napi::bindgen_prelude::ToNapiValue;
+use napi::bindgen_prelude::TypeName;
+use napi::check_status;
+use napi::sys as napi_sys;
+use napi::Env;
+use napi::JsBuffer;
+use napi::JsDate;
+use napi::JsFunction;
+use napi::JsObject;
+use napi::JsUnknown;
+use napi::NapiRaw;
+use napi::NapiValue;
+use napi::ValueType;
+
+use crate::internal::call_method;
+
+// napi_sys only defines napi_bigint with the napi6 feature
+const NAPI_BIGINT: napi_sys::napi_valuetype = 9;
+
+/// An owned copy of a JavaScript value that can be sent to other threads
+/// and converted back into JavaScript later.
+///
+/// Copying supports primitives, arrays, plain objects, `Buffer`s, `Date`s, `Map`s,
+/// `Set`s and BigInts. Symbols, functions and values containing cycles return an error.
+/// Other objects are copied as plain objects of their own enumerable properties.
+///
+/// The napi_ext macros type it as `any`, plain `#[napi]` functions need `ts_arg_type`.
+///
+/// ```ignore
+/// #[napi]
+/// fn process(env: Env, #[napi(ts_arg_type = "any")] input: JsValueOwned) -> napi::Result<JsObject> {
+///   env.spawn_thread(move || {
+///     let output = transform(input);
+///     Ok(move |_env| Ok(output))
+///   })
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsValueOwned {
+  Undefined,
+  Null,
+  Boolean(bool),
+  Number(f64),
+  String(String),
+  /// The decimal representation of the BigInt
+  BigInt(String),
+  Array(Vec<JsValueOwned>),
+  Object(Vec<(String, JsValueOwned)>),
+  /// The contents of a `Buffer` or other `ArrayBufferView`
+  Buffer(Vec<u8>),
+  /// Milliseconds since the Unix epoch
+  Date(f64),
+  Map(Vec<(JsValueOwned, JsValueOwned)>),
+  Set(Vec<JsValueOwned>),
+}
+
+impl JsValueOwned {
+  /// Deep copies a JavaScript value
+  pub fn from_js<V: NapiRaw>(
+    env: &Env,
+    value: V,
+  ) -> napi::Result<Self> {
+    let value = unsafe { JsUnknown::from_raw_unchecked(env.raw(), value.raw()) };
+    Snapshot::new(env)?.copy(value)
+  }
+
+  /// Creates a new JavaScript value from the copy
+  pub fn to_js(
+    &self,
+    env: &Env,
+  ) -> napi::Result<JsUnknown> {
+    let value = match self {
+      Self::Undefined => env.get_undefined()?.into_unknown(),
+      Self::Null => env.get_null()?.into_unknown(),
+      Self::Boolean(value) => env.get_boolean(*value)?.into_unknown(),
+      Self::Number(value) => env.create_double(*value)?.into_unknown(),
+      Self::String(value) => env.create_string(value)?.into_unknown(),
+      Self::BigInt(value) => {
+        global_function(env, "BigInt")?.call(None, &[env.create_string(value)?])?
+      }
+      Self::Array(items) => {
+        let mut array = env.create_array_with_length(items.len())?;
+        for (i, item) in items.iter().enumerate() {
+          array.set_element(i as u32, item.to_js(env)?)?;
+        }
+        array.into_unknown()
+      }
+      Self::Object(entries) => {
+        let mut object = env.create_object()?;
+        for (key, value) in entries {
+          object.set_named_property(key, value.to_js(env)?)?;
+        }
+        object.into_unknown()
+      }
+      Self::Buffer(bytes) => env
+        .create_buffer_with_data(bytes.clone())?
+        .into_raw()
+        .into_unknown(),
+      Self::Date(time) => env.create_date(*time)?.into_unknown(),
+      Self::Map(entries) => {
+        let map = global_function(env, "Map")?.new_instance::<JsUnknown>(&[])?;
+        for (key, value) in entries {
+          call_method(&map, "set", &[key.to_js(env)?, value.to_js(env)?])?;
+        }
+        map.into_unknown()
+      }
+      Self::Set(items) => {
+        let set = global_function(env, "Set")?.new_instance::<JsUnknown>(&[])?;
+        for item in items {
+          call_method(&set, "add", &[item.to_js(env)?])?;
+        }
+        set.into_unknown()
+      }
+    };
+
+    Ok(value)
+  }
+}
+
+/// State for a single deep copy
+struct Snapshot<'a> {
+  env: &'a Env,
+  map_ctor: JsFunction,
+  set_ctor: JsFunction,
+  // Objects currently being copied, used to detect cycles
+  ancestors: Vec<JsObject>,
+}
+
+impl<'a> Snapshot<'a> {
+  fn new(env: &'a Env) -> napi::Result<Self> {
+    Ok(Self {
+      env,
+      map_ctor: global_function(env, "Map")?,
+      set_ctor: global_function(env, "Set")?,
+      ancestors: Vec::new(),
+    })
+  }
+
+  fn copy(
+    &mut self,
+    value: JsUnknown,
+  ) -> napi::Result<JsValueOwned> {
+    let mut value_type = 0;
+    check_status!(unsafe { napi_sys::napi_typeof(self.env.raw(), value.raw(), &mut value_type) })?;
+
+    Ok(match value_type {
+      napi_sys::ValueType::napi_undefined => JsValueOwned::Undefined,
+      napi_sys::ValueType::napi_null => JsValueOwned::Null,
+      napi_sys::ValueType::napi_boolean => {
+        JsValueOwned::Boolean(value.coerce_to_bool()?.get_value()?)
+      }
+      napi_sys::ValueType::napi_number => {
+        JsValueOwned::Number(value.coerce_to_number()?.get_double()?)
+      }
+      napi_sys::ValueType::napi_string => {
+        JsValueOwned::String(value.coerce_to_string()?.into_utf8()?.into_owned()?)
+      }
+      NAPI_BIGINT => JsValueOwned::BigInt(value.coerce_to_string()?.into_utf8()?.into_owned()?),
+      napi_sys::ValueType::napi_object => self.copy_object(unsafe { value.cast() })?,
+      _ => {
+        return Err(napi::Error::from_reason(format!(
+          "Cannot copy a value of type {}",
+          ValueType::from(value_type)
+        )))
+      }
+    })
+  }
+
+  fn copy_object(
+    &mut self,
+    object: JsObject,
+  ) -> napi::Result<JsValueOwned> {
+    if object.is_buffer()? {
+      let buffer = self.cast::<JsBuffer>(&object).into_value()?;
+      return Ok(JsValueOwned::Buffer(buffer.to_vec()));
+    }
+    if object.is_date()? {
+      let date = self.cast::<JsDate>(&object).value_of()?;
+      return Ok(JsValueOwned::Date(date));
+    }
+
+    for ancestor in self.ancestors.iter() {
+      if self.env.strict_equals(ancestor, &object)? {
+        return Err(napi::Error::from_reason(
+          "Cannot copy a value that contains a cycle",
+        ));
+      }
+    }
+
+    self.ancestors.push(self.cast(&object));
+    let result = self.copy_children(&object);
+    self.ancestors.pop();
+    result
+  }
+
+  fn copy_children(
+    &mut self,
+    object: &JsObject,
+  ) -> napi::Result<JsValueOwned> {
+    if object.is_array()? {
+      let mut items = vec![];
+      for i in 0..object.get_array_length()? {
+        items.push(self.copy(object.get_element::<JsUnknown>(i)?)?);
+      }
+      return Ok(JsValueOwned::Array(items));
+    }
+
+    if object.instanceof(&self.map_ctor)? {
+      let mut entries = vec![];
+      for entry in self.entries(object)? {
+        let entry = unsafe { entry.cast::<JsObject>() };
+        let key = self.copy(entry.get_element::<JsUnknown>(0)?)?;
+        let value = self.copy(entry.get_element::<JsUnknown>(1)?)?;
+        entries.push((key, value));
+      }
+      return Ok(JsValueOwned::Map(entries));
+    }
+
+    if object.instanceof(&self.set_ctor)? {
+      let mut items = vec![];
+      for item in self.entries(object)? {
+        items.push(self.copy(item)?);
+      }
+      return Ok(JsValueOwned::Set(items));
+    }
+
+    let keys = call_method(
+      &self
+        .env
+        .get_global()?
+        .get_named_property_unchecked("Object")?,
+      "keys",
+      &[self.cast(object)],
+    )?;
+    let keys = unsafe { keys.cast::<JsObject>() };
+
+    let mut entries = vec![];
+    for i in 0..keys.get_array_length()? {
+      let key = keys
+        .get_element::<JsUnknown>(i)?
+        .coerce_to_string()?
+        .into_utf8()?
+        .into_owned()?;
+      let value = object.get_named_property::<JsUnknown>(&key)?;
+      entries.push((key, self.copy(value)?));
+    }
+    Ok(JsValueOwned::Object(entries))
+  }
+
+  /// Creates another handle to the same value
+  fn cast<V: NapiValue>(
+    &self,
+    value: &impl NapiRaw,
+  ) -> V {
+    unsafe { V::from_raw_unchecked(self.env.raw(), value.raw()) }
+  }
+
+  /// Collects the items of an iterable into a Vec with `Array.from()`
+  fn entries(
+    &self,
+    iterable: &JsObject,
+  ) -> napi::Result<Vec<JsUnknown>> {
+    let array_ctor = self
+      .env
+      .get_global()?
+      .get_named_property_unchecked::<JsObject>("Array")?;
+    let array = call_method(&array_ctor, "from", &[self.cast(iterable)])?;
+    let array = unsafe { array.cast::<JsObject>() };
+
+    (0..array.get_array_length()?)
+      .map(|i| array.get_element::<JsUnknown>(i))
+      .collect()
+  }
+}
+
+fn global_function(
+  env: &Env,
+  name: &str,
+) -> napi::Result<JsFunction> {
+  env.get_global()?.get_named_property_unchecked(name)
+}
+
+impl ToNapiValue for JsValueOwned {
+  unsafe fn to_napi_value(
+    env: napi_sys::napi_env,
+    val: Self,
+  ) -> napi::Result<napi_sys::napi_value> {
+    let env = Env::from_raw(env);
+    Ok(val.to_js(&env)?.raw())
+  }
+}
+
+impl FromNapiValue for JsValueOwned {
+  unsafe fn from_napi_value(
+    env: napi_sys::napi_env,
+    napi_val: napi_sys::napi_value,
+  ) -> napi::Result<Self> {
+    let value = JsUnknown::from_raw_unchecked(env, napi_val);
+    JsValueOwned::from_js(&Env::from_raw(env), value)
+  }
+}
+
+impl TypeName for JsValueOwned {
+  fn type_name() -> &'static str {
+    "any"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Unknown
+  }
+}
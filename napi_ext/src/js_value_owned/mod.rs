@@ -0,0 +1,3 @@
+mod js_value_owned;
+
+pub use self::js_value_owned::*;
@@ -1,11 +1,22 @@
+mod handle_scope;
 mod internal;
+mod js_exception;
+mod js_instance;
 mod js_rc;
+mod js_value_owned;
+mod napi_error;
 mod runtime;
 mod spawn_local;
+pub mod stream;
 mod utils;
 
 pub use napi_ext_macros::*;
 
+pub use self::handle_scope::*;
+pub use self::js_exception::*;
+pub use self::js_instance::*;
 pub use self::js_rc::*;
+pub use self::js_value_owned::*;
+pub use self::napi_error::*;
 pub use self::spawn_local::*;
 pub use self::utils::*;
@@ -0,0 +1,5 @@
+mod napi_error;
+#[doc(hidden)]
+pub mod rejection;
+
+pub use self::napi_error::*;
@@ -0,0 +1,95 @@
+use std::error::Error;
+
+use napi::Env;
+use napi::JsFunction;
+use napi::JsObject;
+
+use crate::JsException;
+
+/// Sets the `code` property of the JavaScript `Error` created from a Rust error.
+///
+/// ```ignore
+/// impl ErrorCode for DbError {
+///   fn code(&self) -> &str {
+///     match self {
+///       DbError::NotFound(_) => "ERR_NOT_FOUND",
+///       DbError::Timeout => "ERR_TIMEOUT",
+///     }
+///   }
+/// }
+/// ```
+pub trait ErrorCode {
+  fn code(&self) -> &str;
+}
+
+/// Converts a Rust error into a [`napi::Error`] holding a JavaScript `Error` with the
+/// same message. Each error in the [`Error::source`] chain becomes the `cause` of the previous one.
+///
+/// ```ignore
+/// let file = std::fs::read(path).map_err(|error| to_napi_error(&env, &error, None))?;
+/// ```
+pub fn to_napi_error<E: Error>(
+  env: &Env,
+  error: &E,
+  code: Option<&str>,
+) -> napi::Error {
+  let mut messages = vec![error.to_string()];
+  let mut source = error.source();
+  while let Some(error) = source {
+    messages.push(error.to_string());
+    source = error.source();
+  }
+  error_chain(env, messages, code)
+}
+
+/// Converts an [`anyhow::Error`] into a [`napi::Error`], keeping its context chain as `cause`s
+#[cfg(feature = "anyhow")]
+pub fn anyhow_to_napi_error(
+  env: &Env,
+  error: &anyhow::Error,
+) -> napi::Error {
+  let messages = error.chain().map(|error| error.to_string()).collect();
+  error_chain(env, messages, None)
+}
+
+fn error_chain(
+  env: &Env,
+  messages: Vec<String>,
+  code: Option<&str>,
+) -> napi::Error {
+  let message = messages[0].clone();
+  match create_error_chain(env, messages, code) {
+    Ok(error) => error,
+    Err(_) => napi::Error::from_reason(message),
+  }
+}
+
+fn create_error_chain(
+  env: &Env,
+  messages: Vec<String>,
+  code: Option<&str>,
+) -> napi::Result<napi::Error> {
+  let error_ctor = env
+    .get_global()?
+    .get_named_property::<JsFunction>("Error")?;
+
+  let mut error = None::<JsObject>;
+  for message in messages.iter().rev() {
+    let mut args = vec![env.create_string(message)?.into_unknown()];
+    if let Some(cause) = error.take() {
+      let mut options = env.create_object()?;
+      options.set_named_property("cause", cause)?;
+      args.push(options.into_unknown());
+    }
+    error = Some(error_ctor.new_instance(&args)?);
+  }
+
+  let Some(mut error) = error else {
+    return Err(napi::Error::from_reason("Error has no message"));
+  };
+  if let Some(code) = code {
+    error.set_named_property("code", env.create_string(code)?)?;
+  }
+
+  Ok(JsException::from_value(env, error)?.into())
+}
@@ -0,0 +1,95 @@
+//! Converts the error of a `#[napi_async]` function into the [`napi::Error`] its Promise rejects with.
+//!
+//! The conversion is picked with autoref specialization, the macro calls
+//! `(&&&&Rejection::new(error)).reject(&env)` so the first of these that applies is used:
+//!
+//! 1. `anyhow::Error`, keeping the context chain
+//! 2. `E: Into<napi::Error>`, as chosen by the error type
+//! 3. `E: std::error::Error + ErrorCode`, keeping the source chain and code
+//! 4. `E: std::error::Error`, keeping the source chain
+use std::cell::Cell;
+use std::error::Error;
+
+use napi::Env;
+
+use super::to_napi_error;
+use super::ErrorCode;
+
+pub struct Rejection<E>(Cell<Option<E>>);
+
+impl<E> Rejection<E> {
+  pub fn new(error: E) -> Self {
+    Self(Cell::new(Some(error)))
+  }
+
+  fn take(&self) -> E {
+    self.0.take().expect("Rejection converted twice")
+  }
+}
+
+#[cfg(feature = "anyhow")]
+pub trait RejectAnyhow {
+  fn reject(
+    &self,
+    env: &Env,
+  ) -> napi::Error;
+}
+
+#[cfg(feature = "anyhow")]
+impl RejectAnyhow for &&&Rejection<anyhow::Error> {
+  fn reject(
+    &self,
+    env: &Env,
+  ) -> napi::Error {
+    super::anyhow_to_napi_error(env, &self.take())
+  }
+}
+
+pub trait RejectInto {
+  fn reject(
+    &self,
+    env: &Env,
+  ) -> napi::Error;
+}
+
+impl<E: Into<napi::Error>> RejectInto for &&Rejection<E> {
+  fn reject(
+    &self,
+    _env: &Env,
+  ) -> napi::Error {
+    self.take().into()
+  }
+}
+
+pub trait RejectErrorCode {
+  fn reject(
+    &self,
+    env: &Env,
+  ) -> napi::Error;
+}
+
+impl<E: Error + ErrorCode> RejectErrorCode for &Rejection<E> {
+  fn reject(
+    &self,
+    env: &Env,
+  ) -> napi::Error {
+    let error = self.take();
+    to_napi_error(env, &error, Some(error.code()))
+  }
+}
+
+pub trait RejectError {
+  fn reject(
+    &self,
+    env: &Env,
+  ) -> napi::Error;
+}
+
+impl<E: Error> RejectError for Rejection<E> {
+  fn reject(
+    &self,
+    env: &Env,
+  ) -> napi::Error {
+    to_napi_error(env, &self.take(), None)
+  }
+}
@@ -155,7 +155,7 @@ impl LocalPool {
       // The pool is empty.
       Poll::Ready(()) => Poll::Ready(()),
       Poll::Pending => {
-        if woken(&thread_notify) {
+        if woken(thread_notify) {
           Poll::Pending
         } else {
           // We're stalled for now.
@@ -7,6 +7,8 @@ use std::ffi::c_void;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::mpsc::channel;
+use std::task::Context;
+use std::task::Poll;
 use std::thread;
 
 use futures::task::LocalSpawnExt;
@@ -18,6 +20,7 @@ use self::executor::LocalPool;
 use self::executor::LocalSpawner;
 use self::executor::ThreadNotify;
 use self::executor::ThreadNotifyRef;
+use crate::handle_scope::HandleScope;
 use crate::internal::declare_threadsafe_function;
 
 type LocalFuture = Pin<Box<dyn Future<Output = ()>>>;
@@ -56,6 +59,47 @@ thread_local! {
   });
 }
 
+// Polls a future within its own handle scope so that handles created
+// by the future are released after each poll rather than accumulating
+// until the thread safe function callback returns
+struct HandleScoped {
+  env: napi_sys::napi_env,
+  fut: Option<LocalFuture>,
+}
+
+impl Future for HandleScoped {
+  type Output = ();
+
+  fn poll(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<()> {
+    let env = unsafe { Env::from_raw(self.env) };
+    // Still poll without a scope so the future is not stalled, its handles are
+    // released with the scope of the thread safe function callback instead
+    let scope = match HandleScope::open(&env) {
+      Ok(scope) => Some(scope),
+      Err(error) => {
+        eprintln!("Uncaught Napi Error: {}", error);
+        None
+      }
+    };
+
+    let result = match self.fut.as_mut() {
+      Some(fut) => fut.as_mut().poll(cx),
+      None => Poll::Ready(()),
+    };
+
+    // Completed futures are dropped within the scope too
+    if result.is_ready() {
+      self.fut.take();
+    }
+
+    drop(scope);
+    result
+  }
+}
+
 // This is the callback for the thread safe function used to drive
 // the futures forward on the main thread
 unsafe extern "C" fn async_runtime_execute(
@@ -70,8 +114,9 @@ unsafe extern "C" fn async_runtime_execute(
   if let Some(fut) = *fut {
     SPAWNER
       .with(move |ls| {
-        ls.spawn_local(async move {
-          fut.await;
+        ls.spawn_local(HandleScoped {
+          env,
+          fut: Some(fut),
         })
       })
       .expect("Unable to spawn future on local pool");
@@ -80,7 +125,7 @@ unsafe extern "C" fn async_runtime_execute(
   let pending_futures = THREAD_NOTIFY.with(|thread_notify| {
     LOCAL_POOL.with(move |lp| {
       let mut lp = lp.borrow_mut();
-      lp.run_until_stalled(&thread_notify)
+      lp.run_until_stalled(thread_notify)
     })
   });
 
@@ -94,6 +139,14 @@ unsafe extern "C" fn async_runtime_execute(
   }
 }
 
+fn init_runtime(env_raw: napi_sys::napi_env) -> *mut napi_sys::napi_threadsafe_function__ {
+  EXECUTE_FUTURES.with(move |tsfn| {
+    *tsfn.get_or_init(move || {
+      declare_threadsafe_function(env_raw, "async_runtime_execute", async_runtime_execute)
+    })
+  })
+}
+
 #[allow(dead_code)]
 pub fn spawn_local<Func, Fut>(
   env: Env,
@@ -105,12 +158,7 @@ pub fn spawn_local<Func, Fut>(
   let env_raw = env.raw();
 
   // Initialize runtime if not already running
-  let tsfn = EXECUTE_FUTURES.with(move |tsfn| {
-    *tsfn
-      .get_or_init(move || {
-        declare_threadsafe_function(env_raw, "async_runtime_execute", async_runtime_execute)
-      })
-  });
+  let tsfn = init_runtime(env_raw);
 
   // Ensure the thread safe function will prevent Nodejs from exiting until the async task is done
   unsafe { napi_sys::napi_ref_threadsafe_function(env_raw, tsfn) };
@@ -124,18 +172,14 @@ pub fn spawn_local<Func, Fut>(
 pub fn spawn_local_fut<Fut>(
   env: Env,
   fut: Fut,
-) -> napi::Result<()> where
+) -> napi::Result<()>
+where
   Fut: Future<Output = ()>,
 {
   let env_raw = env.raw();
 
   // Initialize runtime if not already running
-  let tsfn = EXECUTE_FUTURES.with(move |tsfn| {
-    *tsfn
-      .get_or_init(move || {
-        declare_threadsafe_function(env_raw, "async_runtime_execute", async_runtime_execute)
-      })
-  });
+  let tsfn = init_runtime(env_raw);
 
   // Ensure the thread safe function will prevent Nodejs from exiting until the async task is done
   unsafe { napi_sys::napi_ref_threadsafe_function(env_raw, tsfn) };
@@ -147,3 +191,30 @@ pub fn spawn_local_fut<Fut>(
 
   Ok(())
 }
+
+/// A handle to the runtime of a JavaScript thread that can be sent to other threads
+#[derive(Clone, Copy)]
+pub struct RuntimeHandle(*mut napi_sys::napi_threadsafe_function__);
+
+unsafe impl Send for RuntimeHandle {}
+unsafe impl Sync for RuntimeHandle {}
+
+impl RuntimeHandle {
+  /// Gets the runtime of the current JavaScript thread, initializing it if not already running
+  pub fn current(env: &Env) -> Self {
+    Self(init_runtime(env.raw()))
+  }
+
+  /// Runs the callback on the JavaScript thread from any thread.
+  /// This does not keep the Nodejs process alive
+  pub fn run_on_js_thread<F>(
+    &self,
+    func: F,
+  ) where
+    F: FnOnce() + Send + 'static,
+  {
+    let fut = Box::pin(async move { func() }) as LocalFuture;
+    let fut_ptr = Box::into_raw(Box::new(Some(fut)));
+    unsafe { napi_sys::napi_call_threadsafe_function(self.0, fut_ptr.cast(), 0) };
+  }
+}
@@ -1,7 +1,7 @@
 use futures::Future;
+use napi::bindgen_prelude::ToNapiValue;
 use napi::Env;
 use napi::JsObject;
-use napi::NapiValue;
 
 use crate::runtime;
 use crate::utils::UtilsExt;
@@ -27,14 +27,14 @@ pub fn spawn_local_promise<R, Fut>(
   future: Fut,
 ) -> napi::Result<JsObject>
 where
-  R: NapiValue + 'static,
+  R: ToNapiValue + 'static,
   Fut: Future<Output = napi::Result<R>> + 'static,
 {
   env.create_promise(Box::new(move |env, resolve_func, reject_func| {
     runtime::spawn_local_fut(env, async move {
       match future.await {
         Ok(result) => resolve_func(result),
-        Err(error) => reject_func(error),
+        Err(error) => reject_func(error.into()),
       };
     })
   }))
@@ -45,14 +45,14 @@ pub fn spawn_local_promise2<R, F, Fut>(
   future: Fut,
 ) -> napi::Result<JsObject>
 where
-  R: NapiValue + 'static,
+  R: ToNapiValue + 'static,
   Fut: Future<Output = napi::Result<R>> + 'static,
 {
   env.create_promise(Box::new(move |env, resolve_func, reject_func| {
     runtime::spawn_local_fut(env, async move {
       match future.await {
         Ok(result) => resolve_func(result),
-        Err(error) => reject_func(error),
+        Err(error) => reject_func(error.into()),
       };
     })
   }))
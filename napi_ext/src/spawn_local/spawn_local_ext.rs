@@ -1,14 +1,14 @@
 use futures::Future;
+use napi::bindgen_prelude::ToNapiValue;
 use napi::Env;
 use napi::JsObject;
-use napi::NapiValue;
 
 use crate::spawn_local;
 use crate::spawn_local_promise;
 
 pub trait SpawnLocalExt {
   /// Spawns a non-blocking future on the local thread.
-  /// Normal [`NapiValue`] types can be interacted with in
+  /// Normal [`napi::NapiValue`] types can be interacted with in
   /// the async context. Supports channels, timers, etc.
   ///
   /// Equivalent to:
@@ -17,28 +17,30 @@ pub trait SpawnLocalExt {
   /// setTimeout(async () => { await work() }, 0)
   /// ```
   ///
-  /// To ensure the availability of [`NapiValue`] types beyond the life of the parent function scope,
-  /// ensure that [`NapiValue`] types that will be used in an async closure are wrapped in a [`crate::JsRc`].
+  /// To ensure the availability of [`napi::NapiValue`] types beyond the life of the parent function scope,
+  /// ensure that [`napi::NapiValue`] types that will be used in an async closure are wrapped in a [`crate::JsRc`].
+  /// Each poll of the future runs in its own handle scope, so values held across an `.await`
+  /// must also be wrapped in a [`crate::JsRc`].
   ///
   /// ### Usage:
   ///
   /// #### Running a Callback:
   ///
-  /// ```
+  /// ```ignore
   /// use std::time::Duration;
   ///
   /// use napi::*;
   /// use napi_derive::napi;
-  /// use napi_async_local::JsRc;
-  /// use napi_async_local::JsRcExt;
-  /// use napi_async_local::SpawnLocalExt;
+  /// use napi_ext::JsRc;
+  /// use napi_ext::JsRcExt;
+  /// use napi_ext::SpawnLocalExt;
   /// use async_std::task;
   ///
   /// #[napi]
   /// fn my_js_func(env: Env, callback: JsRc<JsFunction>) -> napi::Result<()> {
-  ///   env.spawn_local(move |env| async move {
+  ///   env.spawn_local(async move {
   ///     task::sleep(Duration::from_millis(1000)).await;
-  ///     callback.inner(&env)?.call_without_args(None)?;
+  ///     callback.borrow(&env)?.call_without_args(None)?;
   ///     Ok(())
   ///   })
   /// }
@@ -46,15 +48,15 @@ pub trait SpawnLocalExt {
   ///
   /// #### Using Channels:
   ///
-  /// ```
+  /// ```ignore
   /// use std::thread;
   /// use std::time::Duration;
   ///
   /// use napi::*;
   /// use napi_derive::napi;
-  /// use napi_async_local::JsRc;
-  /// use napi_async_local::JsRcExt;
-  /// use napi_async_local::SpawnLocalExt;
+  /// use napi_ext::JsRc;
+  /// use napi_ext::JsRcExt;
+  /// use napi_ext::SpawnLocalExt;
   /// use async_std::channel;
   ///
   /// #[napi]
@@ -68,10 +70,10 @@ pub trait SpawnLocalExt {
   ///     }
   ///   });
   ///
-  ///   env.spawn_local(move |env| async move {
+  ///   env.spawn_local(async move {
   ///     while let Ok(value) = rx.recv().await {
   ///       println!("Got number: {}", value);
-  ///       callback.inner(&env)?.call(None, &[env.create_int32(value)?])?;
+  ///       callback.borrow(&env)?.call(None, &[env.create_int32(value)?])?;
   ///     }
   ///
   ///     Ok(())
@@ -86,7 +88,7 @@ pub trait SpawnLocalExt {
     Fut: Future<Output = napi::Result<()>> + 'static;
 
   /// Spawns a non-blocking future on the local thread. Returns a Promise with the value
-  /// returned in the async closure. Normal [`NapiValue`] types can be interacted with in
+  /// returned in the async closure. Normal [`napi::NapiValue`] types can be interacted with in
   /// the async context. Supports channels, timers, etc.
   ///
   /// Equivalent to:
@@ -101,22 +103,32 @@ pub trait SpawnLocalExt {
   /// }, 0)
   /// ```
   ///
-  /// To ensure the availability of [`NapiValue`] types beyond the life of the parent function scope,
-  /// ensure that [`NapiValue`] types that will be used in an async closure are wrapped in a [`crate::JsRc`].
+  /// To ensure the availability of [`napi::NapiValue`] types beyond the life of the parent function scope,
+  /// ensure that [`napi::NapiValue`] types that will be used in an async closure are wrapped in a [`crate::JsRc`].
+  /// Each poll of the future runs in its own handle scope, so values held across an `.await`
+  /// must also be wrapped in a [`crate::JsRc`].
+  ///
+  /// The value can be anything implementing [`ToNapiValue`], including `String`, numbers, `Vec<T>`,
+  /// `Option<T>` and `#[napi(object)]` structs. With the `serde` feature, wrap `Serialize`
+  /// values in `SerdeValue` to convert them through napi's serde support.
+  ///
+  /// Exceptions thrown by JavaScript callbacks and propagated with `?` reject the Promise with
+  /// the original value. To reject with an arbitrary JavaScript value, return a [`crate::JsException`]
+  /// converted into a [`napi::Error`].
   ///
   /// ### Usage:
   ///
-  /// ```
+  /// ```ignore
   /// use std::time::Duration;
   ///
   /// use napi::*;
   /// use napi_derive::napi;
-  /// use napi_async_local::SpawnLocalExt;
+  /// use napi_ext::SpawnLocalExt;
   /// use async_std::task;
   ///
   /// #[napi]
   /// fn my_js_func(env: Env) -> napi::Result<JsObject> {
-  ///   env.spawn_local_promise(move |env| async move {
+  ///   env.spawn_local_promise(async move {
   ///     task::sleep(Duration::from_millis(1000)).await;
   ///     env.create_string("Hello World")
   ///   })
@@ -127,7 +139,7 @@ pub trait SpawnLocalExt {
     future: Fut,
   ) -> napi::Result<JsObject>
   where
-    R: NapiValue + 'static,
+    R: ToNapiValue + 'static,
     Fut: Future<Output = napi::Result<R>> + 'static;
 }
 
@@ -147,7 +159,7 @@ impl SpawnLocalExt for Env {
     future: Fut,
   ) -> napi::Result<JsObject>
   where
-    R: NapiValue + 'static,
+    R: ToNapiValue + 'static,
     Fut: Future<Output = napi::Result<R>> + 'static,
   {
     spawn_local_promise(self, future)
@@ -0,0 +1,69 @@
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use futures::channel::mpsc;
+use futures::Stream;
+use futures::StreamExt;
+use napi::JsObject;
+use napi::JsUnknown;
+
+use super::listeners::Listeners;
+use crate::JsRc;
+
+/// Subscribes to an event on a JavaScript EventEmitter and yields the arguments
+/// of each emission as a [`Stream`] on the local runtime.
+///
+/// The listener is added with `emitter.on(event, ...)` and removed when the
+/// stream is dropped. The stream never ends on its own.
+///
+/// ```ignore
+/// #[napi]
+/// fn on_data(env: Env, socket: JsRc<JsObject>) -> napi::Result<()> {
+///   let mut data = EventStream::new(socket, "data")?.take(10);
+///   env.spawn_local(async move {
+///     while let Some(args) = data.next().await {
+///       env.console_log(&[args[0].get()?])?;
+///     }
+///     Ok(())
+///   })
+/// }
+/// ```
+pub struct EventStream {
+  rx: mpsc::UnboundedReceiver<Vec<JsRc<JsUnknown>>>,
+  _listeners: Listeners,
+}
+
+impl EventStream {
+  pub fn new(
+    emitter: JsRc<JsObject>,
+    event: &str,
+  ) -> napi::Result<Self> {
+    let (tx, rx) = mpsc::unbounded();
+    let mut listeners = Listeners::new(emitter);
+
+    listeners.on(event, move |ctx| {
+      let args = (0..ctx.length)
+        .map(|i| JsRc::new(ctx.env, ctx.get::<JsUnknown>(i)?))
+        .collect::<napi::Result<Vec<_>>>()?;
+      tx.unbounded_send(args).ok();
+      Ok(())
+    })?;
+
+    Ok(Self {
+      rx,
+      _listeners: listeners,
+    })
+  }
+}
+
+impl Stream for EventStream {
+  type Item = Vec<JsRc<JsUnknown>>;
+
+  fn poll_next(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<Option<Self::Item>> {
+    self.rx.poll_next_unpin(cx)
+  }
+}
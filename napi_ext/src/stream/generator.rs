@@ -0,0 +1,109 @@
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::Context;
+use std::task::Poll;
+
+use futures::Stream;
+
+type LocalFuture = Pin<Box<dyn Future<Output = napi::Result<()>>>>;
+
+/// Creates a [`Stream`] from an async closure that sends its values with a [`Yielder`].
+///
+/// The closure only runs while the stream is polled and pauses at each value until
+/// it is taken, so it never runs ahead of the consumer. The stream ends when the
+/// closure returns, and yields the error last if it returns one. Dropping the stream
+/// drops the closure's future.
+///
+/// `#[napi_stream]` async functions are converted into a generator, with
+/// `yield_!(value)` sending the value.
+///
+/// ```ignore
+/// let numbers = generator(|yielder| async move {
+///   for i in 0..10 {
+///     yielder.send(i).await;
+///   }
+///   Ok(())
+/// });
+/// ```
+pub fn generator<T, F, Fut>(f: F) -> Generator<T>
+where
+  F: FnOnce(Yielder<T>) -> Fut,
+  Fut: Future<Output = napi::Result<()>> + 'static,
+{
+  let slot = Rc::new(Cell::new(None));
+  let future = f(Yielder { slot: slot.clone() });
+
+  Generator {
+    slot,
+    future: Some(Box::pin(future)),
+  }
+}
+
+/// A [`Stream`] of the values sent by the closure of [`generator`]
+pub struct Generator<T> {
+  slot: Rc<Cell<Option<T>>>,
+  future: Option<LocalFuture>,
+}
+
+impl<T> Stream for Generator<T> {
+  type Item = napi::Result<T>;
+
+  fn poll_next(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<Option<Self::Item>> {
+    let Some(future) = self.future.as_mut() else {
+      return Poll::Ready(None);
+    };
+
+    let result = future.as_mut().poll(cx);
+    if let Some(value) = self.slot.take() {
+      return Poll::Ready(Some(Ok(value)));
+    }
+
+    match result {
+      Poll::Pending => Poll::Pending,
+      Poll::Ready(result) => {
+        self.future.take();
+        Poll::Ready(result.err().map(Err))
+      }
+    }
+  }
+}
+
+/// Sends the values of a [`generator`]
+pub struct Yielder<T> {
+  slot: Rc<Cell<Option<T>>>,
+}
+
+impl<T> Yielder<T> {
+  /// Sends a value to the consumer of the stream and waits until it is asked for the next one
+  pub async fn send(
+    &self,
+    value: T,
+  ) {
+    self.slot.set(Some(value));
+    YieldNow(false).await
+  }
+}
+
+/// Returns pending once so the generator gives the value in its slot to the stream
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+  type Output = ();
+
+  fn poll(
+    mut self: Pin<&mut Self>,
+    _cx: &mut Context<'_>,
+  ) -> Poll<()> {
+    if self.0 {
+      return Poll::Ready(());
+    }
+    // The stream returns the value straight away and polls again on the next call
+    self.0 = true;
+    Poll::Pending
+  }
+}
@@ -0,0 +1,170 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use futures::ready;
+use futures::Stream;
+use napi::JsFunction;
+use napi::JsObject;
+use napi::JsUnknown;
+use napi::ValueType;
+
+use crate::internal::symbol_async_iterator;
+use crate::JsPromise;
+use crate::JsRc;
+
+type NextFuture = Pin<Box<dyn Future<Output = napi::Result<JsRc<JsUnknown>>>>>;
+
+/// Consumes a JavaScript async iterable (any object implementing `[Symbol.asyncIterator]`)
+/// as a Rust [`Stream`] driven by the local runtime.
+///
+/// Each poll calls `next()` on the iterator and awaits the returned Promise. Dropping the
+/// stream before it is exhausted calls `return()` so the JavaScript side can clean up.
+///
+/// ```ignore
+/// #[napi]
+/// fn consume(env: Env, iterable: JsRc<JsObject>) -> napi::Result<JsObject> {
+///   env.spawn_local_promise(async move {
+///     let mut values = JsAsyncIterator::from(iterable);
+///     while let Some(value) = values.next().await {
+///       env.console_log(&[value?])?;
+///     }
+///     env.get_undefined()
+///   })
+/// }
+/// ```
+pub struct JsAsyncIterator {
+  iterable: JsRc<JsObject>,
+  iterator: Option<JsRc<JsObject>>,
+  next: Option<NextFuture>,
+  done: bool,
+}
+
+impl From<JsRc<JsObject>> for JsAsyncIterator {
+  fn from(iterable: JsRc<JsObject>) -> Self {
+    Self {
+      iterable,
+      iterator: None,
+      next: None,
+      done: false,
+    }
+  }
+}
+
+impl JsAsyncIterator {
+  /// Gets the iterator, calling `[Symbol.asyncIterator]()` on first use
+  fn iterator(&mut self) -> napi::Result<JsObject> {
+    if let Some(iterator) = &self.iterator {
+      return iterator.get();
+    }
+
+    let env = self.iterable.env();
+    let iterable = self.iterable.get()?;
+    let iterator = iterable
+      .get_property::<_, JsFunction>(symbol_async_iterator(&env)?)?
+      .call_without_args(Some(&iterable))?;
+    let iterator = JsObject::try_from(iterator)?;
+
+    self.iterator.replace(JsRc::new(&env, iterator)?);
+    self.iterator()
+  }
+
+  fn call_next(&mut self) -> napi::Result<NextFuture> {
+    let env = self.iterable.env();
+    let iterator = self.iterator()?;
+    let promise = iterator
+      .get_named_property::<JsFunction>("next")?
+      .call_without_args(Some(&iterator))?;
+
+    let future = JsPromise::from_object(&env, JsObject::try_from(promise)?)?.into_future()?;
+    Ok(Box::pin(future))
+  }
+
+  /// Reads the `{ value, done }` object resolved by `next()`
+  fn read_result(
+    &self,
+    result: JsRc<JsUnknown>,
+  ) -> napi::Result<Option<JsRc<JsUnknown>>> {
+    let env = self.iterable.env();
+    let result = JsObject::try_from(result.get()?)?;
+
+    let done = result
+      .get_named_property::<JsUnknown>("done")?
+      .coerce_to_bool()?
+      .get_value()?;
+    if done {
+      return Ok(None);
+    }
+
+    let value = result.get_named_property::<JsUnknown>("value")?;
+    Ok(Some(JsRc::new(&env, value)?))
+  }
+
+  fn call_return(&mut self) -> napi::Result<()> {
+    let Some(iterator) = self.iterator.take() else {
+      return Ok(());
+    };
+
+    let iterator = iterator.get()?;
+    let return_ = iterator.get_named_property::<JsUnknown>("return")?;
+    if return_.get_type()? == ValueType::Function {
+      JsFunction::try_from(return_)?.call_without_args(Some(&iterator))?;
+    }
+
+    Ok(())
+  }
+}
+
+impl Stream for JsAsyncIterator {
+  type Item = napi::Result<JsRc<JsUnknown>>;
+
+  fn poll_next(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<Option<Self::Item>> {
+    let this = self.get_mut();
+    if this.done {
+      return Poll::Ready(None);
+    }
+
+    if this.next.is_none() {
+      match this.call_next() {
+        Ok(next) => this.next = Some(next),
+        Err(error) => {
+          this.done = true;
+          return Poll::Ready(Some(Err(error)));
+        }
+      }
+    }
+
+    let Some(next) = this.next.as_mut() else {
+      return Poll::Ready(None);
+    };
+    let result = ready!(next.as_mut().poll(cx));
+    this.next = None;
+
+    match result.and_then(|result| this.read_result(result)) {
+      Ok(Some(value)) => Poll::Ready(Some(Ok(value))),
+      Ok(None) => {
+        // The iterator finished on its own so there is nothing to return()
+        this.done = true;
+        this.iterator = None;
+        Poll::Ready(None)
+      }
+      Err(error) => {
+        this.done = true;
+        this.iterator = None;
+        Poll::Ready(Some(Err(error)))
+      }
+    }
+  }
+}
+
+impl Drop for JsAsyncIterator {
+  fn drop(&mut self) {
+    if let Err(error) = self.call_return() {
+      eprintln!("Uncaught Napi Error: {}", error);
+    }
+  }
+}
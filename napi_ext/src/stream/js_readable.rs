@@ -0,0 +1,251 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Waker;
+
+use futures::io::AsyncRead;
+use napi::JsBuffer;
+use napi::JsNumber;
+use napi::JsObject;
+use napi::JsUnknown;
+use napi::ValueType;
+
+use super::listeners::Listeners;
+use crate::internal::call_method;
+use crate::JsException;
+use crate::JsRc;
+
+const DEFAULT_HIGH_WATER_MARK: usize = 16 * 1024;
+
+enum Chunk {
+  // Buffers are read from directly rather than copied into Rust
+  Buffer(JsRc<JsBuffer>),
+  Bytes(Vec<u8>),
+}
+
+#[derive(Default)]
+struct ReadableState {
+  chunks: VecDeque<Chunk>,
+  // Bytes already read from the front chunk
+  offset: usize,
+  buffered: usize,
+  paused: bool,
+  ended: bool,
+  closed: bool,
+  // Kept so every read after an error fails with it
+  error: Option<JsRc<JsUnknown>>,
+  waker: Option<Waker>,
+}
+
+impl ReadableState {
+  fn wake(&mut self) {
+    if let Some(waker) = self.waker.take() {
+      waker.wake();
+    }
+  }
+}
+
+/// Reads from a Node.js `stream.Readable` as a [`futures::io::AsyncRead`] on the local runtime.
+///
+/// Chunks emitted by `data` events are buffered until read. When more than the stream's
+/// `readableHighWaterMark` is buffered, the Readable is paused until the reader catches up.
+/// `end` resolves reads with `0` bytes and `error` fails every later read. A stream that is
+/// destroyed before it ends fails reads with [`io::ErrorKind::UnexpectedEof`].
+///
+/// ```ignore
+/// #[napi]
+/// fn read_all(env: Env, readable: JsRc<JsObject>) -> napi::Result<JsObject> {
+///   env.spawn_local_promise(async move {
+///     let mut bytes = vec![];
+///     JsReadable::new(readable)?.read_to_end(&mut bytes).await?;
+///     Ok(bytes.len() as u32)
+///   })
+/// }
+/// ```
+pub struct JsReadable {
+  readable: JsRc<JsObject>,
+  state: Rc<RefCell<ReadableState>>,
+  high_water_mark: usize,
+  _listeners: Listeners,
+}
+
+impl JsReadable {
+  pub fn new(readable: JsRc<JsObject>) -> napi::Result<Self> {
+    let state = Rc::new(RefCell::new(ReadableState::default()));
+    let high_water_mark = match readable
+      .get()?
+      .get_named_property::<JsUnknown>("readableHighWaterMark")?
+    {
+      value if value.get_type()? == ValueType::Number => {
+        JsNumber::try_from(value)?.get_uint32()? as usize
+      }
+      _ => DEFAULT_HIGH_WATER_MARK,
+    };
+
+    let mut listeners = Listeners::new(readable.clone());
+
+    listeners.on("data", {
+      let state = state.clone();
+      move |ctx| {
+        let data = ctx.get::<JsUnknown>(0)?;
+        let chunk = if data.is_buffer()? {
+          Chunk::Buffer(JsRc::new(ctx.env, JsBuffer::try_from(data)?)?)
+        } else {
+          // Streams with an encoding set emit strings
+          Chunk::Bytes(data.coerce_to_string()?.into_utf8()?.as_slice().to_vec())
+        };
+
+        let pause = {
+          let mut state = state.borrow_mut();
+          state.buffered += chunk_len(&chunk)?;
+          state.chunks.push_back(chunk);
+          state.wake();
+          let pause = !state.paused && state.buffered >= high_water_mark;
+          state.paused |= pause;
+          pause
+        };
+
+        if pause {
+          call_method(&ctx.this::<JsObject>()?, "pause", &[])?;
+        }
+
+        Ok(())
+      }
+    })?;
+
+    listeners.on("end", {
+      let state = state.clone();
+      move |_ctx| {
+        let mut state = state.borrow_mut();
+        state.ended = true;
+        state.wake();
+        Ok(())
+      }
+    })?;
+
+    // A destroyed stream emits "close" without "end"
+    listeners.on("close", {
+      let state = state.clone();
+      move |_ctx| {
+        let mut state = state.borrow_mut();
+        state.closed = true;
+        state.wake();
+        Ok(())
+      }
+    })?;
+
+    listeners.on("error", {
+      let state = state.clone();
+      move |ctx| {
+        let mut state = state.borrow_mut();
+        // destroy(value) can emit any value, not only Errors
+        state.error = Some(JsRc::new(ctx.env, ctx.get::<JsUnknown>(0)?)?);
+        state.wake();
+        Ok(())
+      }
+    })?;
+
+    Ok(Self {
+      readable,
+      state,
+      high_water_mark,
+      _listeners: listeners,
+    })
+  }
+
+  fn read_chunks(
+    &self,
+    buf: &mut [u8],
+  ) -> napi::Result<usize> {
+    let mut state = self.state.borrow_mut();
+    let mut written = 0;
+
+    while written < buf.len() {
+      let Some(chunk) = state.chunks.front() else {
+        break;
+      };
+
+      let buffer_value;
+      let bytes = match chunk {
+        Chunk::Buffer(buffer) => {
+          buffer_value = buffer.get()?.into_value()?;
+          &buffer_value[state.offset..]
+        }
+        Chunk::Bytes(bytes) => &bytes[state.offset..],
+      };
+
+      let len = bytes.len().min(buf.len() - written);
+      buf[written..written + len].copy_from_slice(&bytes[..len]);
+      written += len;
+
+      if len == bytes.len() {
+        state.chunks.pop_front();
+        state.offset = 0;
+      } else {
+        state.offset += len;
+      }
+    }
+
+    state.buffered -= written;
+    Ok(written)
+  }
+
+  fn resume_if_drained(&self) -> napi::Result<()> {
+    let resume = {
+      let mut state = self.state.borrow_mut();
+      let resume = state.paused && state.buffered < self.high_water_mark;
+      state.paused &= !resume;
+      resume
+    };
+
+    if resume {
+      call_method(&self.readable.get()?, "resume", &[])?;
+    }
+    Ok(())
+  }
+}
+
+impl AsyncRead for JsReadable {
+  fn poll_read(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    buf: &mut [u8],
+  ) -> Poll<io::Result<usize>> {
+    let this = self.get_mut();
+
+    let written = this.read_chunks(buf).map_err(io::Error::other)?;
+    this.resume_if_drained().map_err(io::Error::other)?;
+    if written > 0 || buf.is_empty() {
+      return Poll::Ready(Ok(written));
+    }
+
+    let mut state = this.state.borrow_mut();
+    if let Some(error) = &state.error {
+      let error = napi::Error::from(JsException::from(error.clone()));
+      return Poll::Ready(Err(io::Error::other(error)));
+    }
+    if state.ended {
+      return Poll::Ready(Ok(0));
+    }
+    if state.closed {
+      return Poll::Ready(Err(io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "the stream was closed before it ended",
+      )));
+    }
+
+    state.waker = Some(cx.waker().clone());
+    Poll::Pending
+  }
+}
+
+fn chunk_len(chunk: &Chunk) -> napi::Result<usize> {
+  Ok(match chunk {
+    Chunk::Buffer(buffer) => buffer.get()?.into_value()?.len(),
+    Chunk::Bytes(bytes) => bytes.len(),
+  })
+}
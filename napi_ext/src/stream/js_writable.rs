@@ -0,0 +1,185 @@
+use std::cell::RefCell;
+use std::io;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Waker;
+
+use futures::io::AsyncWrite;
+use napi::JsObject;
+use napi::JsUnknown;
+
+use super::listeners::Listeners;
+use crate::internal::call_method;
+use crate::JsException;
+use crate::JsRc;
+
+#[derive(Default)]
+struct WritableState {
+  needs_drain: bool,
+  ending: bool,
+  finished: bool,
+  error: Option<napi::Error>,
+  waker: Option<Waker>,
+}
+
+impl WritableState {
+  fn wake(&mut self) {
+    if let Some(waker) = self.waker.take() {
+      waker.wake();
+    }
+  }
+}
+
+/// Writes to a Node.js `stream.Writable` as a [`futures::io::AsyncWrite`] on the local runtime.
+///
+/// When `write()` returns `false` further writes wait for the `drain` event. Closing
+/// calls `end()` and waits for `finish`. An `error` event fails the next operation,
+/// and writes fail with [`io::ErrorKind::BrokenPipe`] once the stream has closed.
+///
+/// ```ignore
+/// #[napi]
+/// fn write_hello(env: Env, writable: JsRc<JsObject>) -> napi::Result<JsObject> {
+///   env.spawn_local_promise(async move {
+///     let mut writable = JsWritable::new(writable)?;
+///     writable.write_all(b"Hello World").await?;
+///     writable.close().await?;
+///     env.get_undefined()
+///   })
+/// }
+/// ```
+pub struct JsWritable {
+  writable: JsRc<JsObject>,
+  state: Rc<RefCell<WritableState>>,
+  _listeners: Listeners,
+}
+
+impl JsWritable {
+  pub fn new(writable: JsRc<JsObject>) -> napi::Result<Self> {
+    let state = Rc::new(RefCell::new(WritableState::default()));
+    let mut listeners = Listeners::new(writable.clone());
+
+    listeners.on("drain", {
+      let state = state.clone();
+      move |_ctx| {
+        let mut state = state.borrow_mut();
+        state.needs_drain = false;
+        state.wake();
+        Ok(())
+      }
+    })?;
+
+    // A destroyed stream emits "close" without "finish"
+    for event in ["finish", "close"] {
+      listeners.on(event, {
+        let state = state.clone();
+        move |_ctx| {
+          let mut state = state.borrow_mut();
+          state.finished = true;
+          state.wake();
+          Ok(())
+        }
+      })?;
+    }
+
+    listeners.on("error", {
+      let state = state.clone();
+      move |ctx| {
+        let mut state = state.borrow_mut();
+        // destroy(value) can emit any value, not only Errors
+        let error = JsException::from_value(ctx.env, ctx.get::<JsUnknown>(0)?)?;
+        state.error = Some(error.into());
+        state.wake();
+        Ok(())
+      }
+    })?;
+
+    Ok(Self {
+      writable,
+      state,
+      _listeners: listeners,
+    })
+  }
+
+  /// Returns an error or registers the waker if the stream cannot be written to yet
+  fn poll_ready(
+    &self,
+    cx: &mut Context<'_>,
+  ) -> Poll<io::Result<()>> {
+    let mut state = self.state.borrow_mut();
+    if let Some(error) = state.error.take() {
+      return Poll::Ready(Err(io::Error::other(error)));
+    }
+    // A write waiting for "drain" is woken by "close" when the stream is destroyed
+    if state.finished {
+      return Poll::Ready(Err(io::Error::new(
+        io::ErrorKind::BrokenPipe,
+        "the stream is closed",
+      )));
+    }
+    if state.needs_drain {
+      state.waker = Some(cx.waker().clone());
+      return Poll::Pending;
+    }
+    Poll::Ready(Ok(()))
+  }
+
+  fn write(
+    &self,
+    buf: &[u8],
+  ) -> napi::Result<bool> {
+    let env = self.writable.env();
+    let chunk = env.create_buffer_with_data(buf.to_vec())?.into_raw();
+
+    let writable = self.writable.get()?;
+    call_method(&writable, "write", &[chunk.into_unknown()])?
+      .coerce_to_bool()?
+      .get_value()
+  }
+}
+
+impl AsyncWrite for JsWritable {
+  fn poll_write(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    buf: &[u8],
+  ) -> Poll<io::Result<usize>> {
+    if let Err(error) = futures::ready!(self.poll_ready(cx)) {
+      return Poll::Ready(Err(error));
+    }
+
+    let flushed = self.write(buf).map_err(io::Error::other)?;
+    self.state.borrow_mut().needs_drain = !flushed;
+    Poll::Ready(Ok(buf.len()))
+  }
+
+  fn poll_flush(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<io::Result<()>> {
+    self.poll_ready(cx)
+  }
+
+  fn poll_close(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<io::Result<()>> {
+    if !self.state.borrow().ending {
+      self.state.borrow_mut().ending = true;
+      call_method(&self.writable.get().map_err(io::Error::other)?, "end", &[])
+        .map_err(io::Error::other)?;
+    }
+
+    let mut state = self.state.borrow_mut();
+    if let Some(error) = state.error.take() {
+      return Poll::Ready(Err(io::Error::other(error)));
+    }
+    if state.finished {
+      return Poll::Ready(Ok(()));
+    }
+
+    state.waker = Some(cx.waker().clone());
+    Poll::Pending
+  }
+}
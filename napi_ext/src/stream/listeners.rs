@@ -0,0 +1,71 @@
+use napi::CallContext;
+use napi::JsFunction;
+use napi::JsObject;
+
+use crate::JsRc;
+
+/// Event listeners attached to a JavaScript EventEmitter that are
+/// removed again when dropped
+pub(crate) struct Listeners {
+  emitter: JsRc<JsObject>,
+  listeners: Vec<(String, JsRc<JsFunction>)>,
+}
+
+impl Listeners {
+  pub fn new(emitter: JsRc<JsObject>) -> Self {
+    Self {
+      emitter,
+      listeners: Vec::new(),
+    }
+  }
+
+  /// Calls `emitter.on(event, listener)`
+  pub fn on<F>(
+    &mut self,
+    event: &str,
+    callback: F,
+  ) -> napi::Result<()>
+  where
+    F: Fn(CallContext<'_>) -> napi::Result<()> + 'static,
+  {
+    let env = self.emitter.env();
+    let listener = env.create_function_from_closure(event, move |ctx| {
+      let env = *ctx.env;
+      callback(ctx)?;
+      env.get_undefined()
+    })?;
+    let listener = JsRc::new(&env, listener)?;
+
+    self.call("on", event, &listener)?;
+    self.listeners.push((event.to_string(), listener));
+    Ok(())
+  }
+
+  fn call(
+    &self,
+    method: &str,
+    event: &str,
+    listener: &JsRc<JsFunction>,
+  ) -> napi::Result<()> {
+    let env = self.emitter.env();
+    let emitter = self.emitter.get()?;
+    let args = [
+      env.create_string(event)?.into_unknown(),
+      listener.get()?.into_unknown(),
+    ];
+
+    emitter
+      .get_named_property::<JsFunction>(method)?
+      .call(Some(&emitter), &args)?;
+
+    Ok(())
+  }
+}
+
+impl Drop for Listeners {
+  fn drop(&mut self) {
+    for (event, listener) in self.listeners.iter() {
+      self.call("removeListener", event, listener).ok();
+    }
+  }
+}
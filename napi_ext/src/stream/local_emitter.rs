@@ -0,0 +1,111 @@
+use futures::channel::mpsc;
+use futures::StreamExt;
+use napi::bindgen_prelude::ToNapiValue;
+use napi::Env;
+use napi::JsObject;
+use napi::JsUnknown;
+use napi::NapiValue;
+
+use crate::internal::call_method;
+use crate::JsRc;
+use crate::SpawnLocalExt;
+
+type MapJsArgs = Box<dyn Send + FnOnce(&Env) -> napi::Result<Vec<JsUnknown>> + 'static>;
+
+struct Emission {
+  event: String,
+  map_args: MapJsArgs,
+}
+
+/// A [`Send`] handle that calls `emit` on a JavaScript EventEmitter.
+///
+/// Emissions are sent through a channel and emitted on the JavaScript thread by a task
+/// on the local runtime. The task (and the Nodejs process) stays alive until every
+/// clone of the handle is dropped.
+///
+/// ```ignore
+/// #[napi]
+/// fn start(env: Env, emitter: JsRc<JsObject>) -> napi::Result<()> {
+///   let emitter = LocalEmitter::new(&env, emitter)?;
+///   thread::spawn(move || {
+///     for i in 0..10 {
+///       emitter.emit("progress", i).unwrap();
+///     }
+///   });
+///   Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct LocalEmitter {
+  tx: mpsc::UnboundedSender<Emission>,
+}
+
+impl LocalEmitter {
+  pub fn new(
+    env: &Env,
+    emitter: JsRc<JsObject>,
+  ) -> napi::Result<Self> {
+    let (tx, mut rx) = mpsc::unbounded::<Emission>();
+
+    env.spawn_local({
+      let env = *env;
+      async move {
+        while let Some(emission) = rx.next().await {
+          if let Err(error) = emit(&env, &emitter, emission) {
+            eprintln!("Uncaught Napi Error: {}", error);
+          }
+        }
+        Ok(())
+      }
+    })?;
+
+    Ok(Self { tx })
+  }
+
+  /// Emits `event` with a single argument
+  pub fn emit<T>(
+    &self,
+    event: &str,
+    value: T,
+  ) -> napi::Result<()>
+  where
+    T: ToNapiValue + Send + 'static,
+  {
+    self.emit_with(event, move |env| {
+      let value = unsafe { T::to_napi_value(env.raw(), value)? };
+      Ok(vec![unsafe {
+        JsUnknown::from_raw_unchecked(env.raw(), value)
+      }])
+    })
+  }
+
+  /// Emits `event` with the arguments returned from `map_args`,
+  /// which is called on the JavaScript thread
+  pub fn emit_with<F>(
+    &self,
+    event: &str,
+    map_args: F,
+  ) -> napi::Result<()>
+  where
+    F: FnOnce(&Env) -> napi::Result<Vec<JsUnknown>> + Send + 'static,
+  {
+    self
+      .tx
+      .unbounded_send(Emission {
+        event: event.to_string(),
+        map_args: Box::new(map_args),
+      })
+      .map_err(|_| napi::Error::from_reason("LocalEmitter is no longer running"))
+  }
+}
+
+fn emit(
+  env: &Env,
+  emitter: &JsRc<JsObject>,
+  emission: Emission,
+) -> napi::Result<()> {
+  let mut args = vec![env.create_string(&emission.event)?.into_unknown()];
+  args.extend((emission.map_args)(env)?);
+  call_method(&emitter.get()?, "emit", &args)?;
+  Ok(())
+}
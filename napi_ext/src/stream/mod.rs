@@ -0,0 +1,22 @@
+mod event_stream;
+mod generator;
+mod js_async_iterator;
+mod js_readable;
+mod js_writable;
+mod listeners;
+mod local_emitter;
+mod to_async_iterator;
+mod to_readable;
+mod to_readable_stream;
+mod to_writable_stream;
+
+pub use self::event_stream::*;
+pub use self::generator::*;
+pub use self::js_async_iterator::*;
+pub use self::js_readable::*;
+pub use self::js_writable::*;
+pub use self::local_emitter::*;
+pub use self::to_async_iterator::*;
+pub use self::to_readable::*;
+pub use self::to_readable_stream::*;
+pub use self::to_writable_stream::*;
@@ -0,0 +1,109 @@
+use std::pin::Pin;
+use std::rc::Rc;
+
+use futures::lock::Mutex;
+use futures::Stream;
+use futures::StreamExt;
+use napi::bindgen_prelude::ToNapiValue;
+use napi::Env;
+use napi::JsObject;
+use napi::JsUnknown;
+use napi::NapiValue;
+
+use crate::internal::symbol_async_iterator;
+use crate::SpawnLocalExt;
+
+const SYM_ASYNC_ITERATOR: &str = "napi::async_iterator";
+const SYM_ASYNC_ITERATOR_NEXT: &str = "napi::async_iterator::next";
+const SYM_ASYNC_ITERATOR_RETURN: &str = "napi::async_iterator::return";
+
+type LocalStream<T> = Pin<Box<dyn Stream<Item = napi::Result<T>>>>;
+
+/// Converts a local [`Stream`] into a JavaScript object implementing the
+/// async iterator protocol, so it can be consumed with `for await`.
+///
+/// The stream is only polled on the local runtime when JavaScript calls `next()`,
+/// so a slow consumer applies backpressure to the stream. Calling `return()`
+/// (e.g. breaking out of a `for await` loop) drops the stream.
+///
+/// ```javascript
+/// for await (const value of napi.myStream()) {
+///   console.log(value)
+/// }
+/// ```
+pub fn to_async_iterator<T, S>(
+  env: &Env,
+  stream: S,
+) -> napi::Result<JsObject>
+where
+  T: ToNapiValue + 'static,
+  S: Stream<Item = napi::Result<T>> + 'static,
+{
+  // Calls to next() are queued behind the lock so values are yielded in order
+  let stream = Rc::new(Mutex::new(Some(Box::pin(stream) as LocalStream<T>)));
+
+  let next = env.create_function_from_closure(SYM_ASYNC_ITERATOR_NEXT, {
+    let stream = stream.clone();
+    move |ctx| {
+      let env = *ctx.env;
+      let stream = stream.clone();
+
+      env.spawn_local_promise(async move {
+        let mut stream = stream.lock().await;
+        let Some(inner) = stream.as_mut() else {
+          return iterator_result(&env, None);
+        };
+
+        match inner.next().await {
+          Some(Ok(value)) => {
+            let value = unsafe {
+              JsUnknown::from_raw_unchecked(env.raw(), T::to_napi_value(env.raw(), value)?)
+            };
+            iterator_result(&env, Some(value))
+          }
+          Some(Err(error)) => {
+            stream.take();
+            Err(error)
+          }
+          None => {
+            stream.take();
+            iterator_result(&env, None)
+          }
+        }
+      })
+    }
+  })?;
+
+  let return_ = env.create_function_from_closure(SYM_ASYNC_ITERATOR_RETURN, move |ctx| {
+    let env = *ctx.env;
+    let stream = stream.clone();
+
+    env.spawn_local_promise(async move {
+      stream.lock().await.take();
+      iterator_result(&env, None)
+    })
+  })?;
+
+  let async_iterator =
+    env.create_function_from_closure(SYM_ASYNC_ITERATOR, |ctx| ctx.this::<JsObject>())?;
+
+  let mut iterator = env.create_object()?;
+  iterator.set_named_property("next", next)?;
+  iterator.set_named_property("return", return_)?;
+  iterator.set_property(symbol_async_iterator(env)?, async_iterator)?;
+
+  Ok(iterator)
+}
+
+/// Creates the `{ value, done }` object resolved by `next()` and `return()`
+fn iterator_result(
+  env: &Env,
+  value: Option<JsUnknown>,
+) -> napi::Result<JsObject> {
+  let mut result = env.create_object()?;
+  result.set_named_property("done", value.is_none())?;
+  if let Some(value) = value {
+    result.set_named_property("value", value)?;
+  }
+  Ok(result)
+}
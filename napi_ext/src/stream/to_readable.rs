@@ -0,0 +1,109 @@
+use std::pin::Pin;
+use std::rc::Rc;
+
+use futures::io::AsyncRead;
+use futures::io::AsyncReadExt;
+use futures::lock::Mutex;
+use napi::Env;
+use napi::JsFunction;
+use napi::JsNumber;
+use napi::JsObject;
+use napi::JsUnknown;
+use napi::ValueType;
+
+use crate::internal::call_method;
+use crate::internal::require_builtin;
+use crate::JsRc;
+use crate::SpawnLocalExt;
+
+const SYM_READABLE_READ: &str = "napi::readable::read";
+const SYM_READABLE_DESTROY: &str = "napi::readable::destroy";
+const DEFAULT_READ_SIZE: usize = 16 * 1024;
+
+type LocalReader = Pin<Box<dyn AsyncRead>>;
+
+/// Exposes a [`futures::io::AsyncRead`] to JavaScript as a Node.js `stream.Readable`.
+///
+/// The reader is only read on the local runtime when the Readable calls `_read()`,
+/// so backpressure from JavaScript consumers is respected. Chunks are moved into
+/// `Buffer`s without copying. Destroying the Readable drops the reader.
+pub fn to_readable<R>(
+  env: &Env,
+  reader: R,
+) -> napi::Result<JsObject>
+where
+  R: AsyncRead + 'static,
+{
+  let reader = Rc::new(Mutex::new(Some(Box::pin(reader) as LocalReader)));
+
+  let read = env.create_function_from_closure(SYM_READABLE_READ, {
+    let reader = reader.clone();
+    move |ctx| {
+      let env = *ctx.env;
+      let readable = JsRc::new(&env, ctx.this::<JsObject>()?)?;
+      let size = match ctx.get::<JsUnknown>(0)? {
+        size if size.get_type()? == ValueType::Number => {
+          JsNumber::try_from(size)?.get_uint32()? as usize
+        }
+        _ => DEFAULT_READ_SIZE,
+      };
+
+      let reader = reader.clone();
+      env.spawn_local(async move {
+        let mut reader = reader.lock().await;
+        let Some(inner) = reader.as_mut() else {
+          return Ok(());
+        };
+
+        let mut buf = vec![0; size.max(1)];
+        let result = inner.read(&mut buf).await;
+        let readable = readable.get()?;
+
+        match result {
+          Ok(0) => {
+            reader.take();
+            call_method(&readable, "push", &[env.get_null()?.into_unknown()])?;
+          }
+          Ok(len) => {
+            buf.truncate(len);
+            let chunk = env.create_buffer_with_data(buf)?.into_raw();
+            call_method(&readable, "push", &[chunk.into_unknown()])?;
+          }
+          Err(error) => {
+            reader.take();
+            let error = env.create_error(napi::Error::from_reason(error.to_string()))?;
+            call_method(&readable, "destroy", &[error.into_unknown()])?;
+          }
+        };
+
+        Ok(())
+      })?;
+
+      env.get_undefined()
+    }
+  })?;
+
+  let destroy = env.create_function_from_closure(SYM_READABLE_DESTROY, move |ctx| {
+    let env = *ctx.env;
+    let error = ctx.get::<JsUnknown>(0)?;
+    let callback = ctx.get::<JsFunction>(1)?;
+
+    // A pending read holds the lock, the reader is dropped once it completes
+    let reader = reader.clone();
+    env.spawn_local(async move {
+      reader.lock().await.take();
+      Ok(())
+    })?;
+
+    callback.call(None, &[error])?;
+    env.get_undefined()
+  })?;
+
+  let mut options = env.create_object()?;
+  options.set_named_property("read", read)?;
+  options.set_named_property("destroy", destroy)?;
+
+  let readable_ctor =
+    require_builtin(env, "stream")?.get_named_property::<JsFunction>("Readable")?;
+  readable_ctor.new_instance(&[options])
+}
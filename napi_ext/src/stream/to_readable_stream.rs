@@ -0,0 +1,100 @@
+use std::pin::Pin;
+use std::rc::Rc;
+
+use futures::lock::Mutex;
+use futures::Stream;
+use futures::StreamExt;
+use napi::bindgen_prelude::ToNapiValue;
+use napi::Env;
+use napi::JsObject;
+use napi::JsUnknown;
+use napi::NapiValue;
+
+use crate::internal::call_method;
+use crate::internal::web_stream_ctor;
+use crate::JsRc;
+use crate::SpawnLocalExt;
+
+const SYM_READABLE_STREAM_PULL: &str = "napi::readable_stream::pull";
+const SYM_READABLE_STREAM_CANCEL: &str = "napi::readable_stream::cancel";
+
+type LocalStream<T> = Pin<Box<dyn Stream<Item = napi::Result<T>>>>;
+
+/// Converts a local [`Stream`] into a WHATWG `ReadableStream`.
+///
+/// The underlying source is pull-based: each `pull()` returns a Promise that polls
+/// the stream once on the local runtime and enqueues the value. The stream ends
+/// when it returns `None`, errors when it yields an `Err`, and is dropped on `cancel()`.
+///
+/// ```ignore
+/// #[napi]
+/// fn fetch_body(env: Env) -> napi::Result<JsObject> {
+///   let body = stream::to_readable_stream(&env, chunks())?;
+///   let response_ctor: JsFunction = env.get_global()?.get_named_property("Response")?;
+///   response_ctor.new_instance(&[body])
+/// }
+/// ```
+pub fn to_readable_stream<T, S>(
+  env: &Env,
+  stream: S,
+) -> napi::Result<JsObject>
+where
+  T: ToNapiValue + 'static,
+  S: Stream<Item = napi::Result<T>> + 'static,
+{
+  let stream = Rc::new(Mutex::new(Some(Box::pin(stream) as LocalStream<T>)));
+
+  let pull = env.create_function_from_closure(SYM_READABLE_STREAM_PULL, {
+    let stream = stream.clone();
+    move |ctx| {
+      let env = *ctx.env;
+      let controller = JsRc::new(&env, ctx.get::<JsObject>(0)?)?;
+      let stream = stream.clone();
+
+      env.spawn_local_promise(async move {
+        let mut stream = stream.lock().await;
+        let Some(inner) = stream.as_mut() else {
+          return env.get_undefined();
+        };
+
+        let next = inner.next().await;
+        let controller = controller.get()?;
+        match next {
+          Some(Ok(value)) => {
+            let value = unsafe {
+              JsUnknown::from_raw_unchecked(env.raw(), T::to_napi_value(env.raw(), value)?)
+            };
+            call_method(&controller, "enqueue", &[value])?;
+          }
+          Some(Err(error)) => {
+            // Rejecting pull() errors the ReadableStream
+            stream.take();
+            return Err(error);
+          }
+          None => {
+            stream.take();
+            call_method(&controller, "close", &[])?;
+          }
+        };
+
+        env.get_undefined()
+      })
+    }
+  })?;
+
+  let cancel = env.create_function_from_closure(SYM_READABLE_STREAM_CANCEL, move |ctx| {
+    let env = *ctx.env;
+    let stream = stream.clone();
+
+    env.spawn_local_promise(async move {
+      stream.lock().await.take();
+      env.get_undefined()
+    })
+  })?;
+
+  let mut source = env.create_object()?;
+  source.set_named_property("pull", pull)?;
+  source.set_named_property("cancel", cancel)?;
+
+  web_stream_ctor(env, "ReadableStream")?.new_instance(&[source])
+}
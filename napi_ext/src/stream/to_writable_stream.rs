@@ -0,0 +1,102 @@
+use std::pin::Pin;
+use std::rc::Rc;
+
+use futures::lock::Mutex;
+use futures::Sink;
+use futures::SinkExt;
+use napi::bindgen_prelude::FromNapiValue;
+use napi::Env;
+use napi::JsObject;
+use napi::JsUnknown;
+use napi::NapiRaw;
+
+use crate::internal::web_stream_ctor;
+use crate::SpawnLocalExt;
+
+const SYM_WRITABLE_STREAM_WRITE: &str = "napi::writable_stream::write";
+const SYM_WRITABLE_STREAM_CLOSE: &str = "napi::writable_stream::close";
+const SYM_WRITABLE_STREAM_ABORT: &str = "napi::writable_stream::abort";
+
+type LocalSink<T> = Pin<Box<dyn Sink<T, Error = napi::Error>>>;
+
+/// Creates a WHATWG `WritableStream` whose underlying sink writes into a Rust [`Sink`].
+///
+/// Chunks are converted with [`FromNapiValue`] when `write()` is called and sent into
+/// the sink on the local runtime. The Promise returned from `write()` resolves once the
+/// sink accepts the value, which applies backpressure to the writer. `close()` closes
+/// the sink and `abort()` drops it.
+///
+/// ```ignore
+/// #[napi]
+/// fn collect(env: Env) -> napi::Result<JsObject> {
+///   let (tx, rx) = futures::channel::mpsc::channel::<String>(16);
+///   env.spawn_local(async move {
+///     let lines = rx.collect::<Vec<_>>().await;
+///     println!("{:?}", lines);
+///     Ok(())
+///   })?;
+///   stream::to_writable_stream(&env, tx.sink_map_err(|e| napi::Error::from_reason(e.to_string())))
+/// }
+/// ```
+pub fn to_writable_stream<T, S>(
+  env: &Env,
+  sink: S,
+) -> napi::Result<JsObject>
+where
+  T: FromNapiValue + 'static,
+  S: Sink<T, Error = napi::Error> + 'static,
+{
+  let sink = Rc::new(Mutex::new(Some(Box::pin(sink) as LocalSink<T>)));
+
+  let write = env.create_function_from_closure(SYM_WRITABLE_STREAM_WRITE, {
+    let sink = sink.clone();
+    move |ctx| {
+      let env = *ctx.env;
+      let chunk = ctx.get::<JsUnknown>(0)?;
+      let value = unsafe { T::from_napi_value(env.raw(), chunk.raw())? };
+      let sink = sink.clone();
+
+      env.spawn_local_promise(async move {
+        let mut sink = sink.lock().await;
+        let Some(inner) = sink.as_mut() else {
+          return Err(napi::Error::from_reason("WritableStream is closed"));
+        };
+
+        inner.send(value).await?;
+        env.get_undefined()
+      })
+    }
+  })?;
+
+  let close = env.create_function_from_closure(SYM_WRITABLE_STREAM_CLOSE, {
+    let sink = sink.clone();
+    move |ctx| {
+      let env = *ctx.env;
+      let sink = sink.clone();
+
+      env.spawn_local_promise(async move {
+        if let Some(mut inner) = sink.lock().await.take() {
+          inner.close().await?;
+        }
+        env.get_undefined()
+      })
+    }
+  })?;
+
+  let abort = env.create_function_from_closure(SYM_WRITABLE_STREAM_ABORT, move |ctx| {
+    let env = *ctx.env;
+    let sink = sink.clone();
+
+    env.spawn_local_promise(async move {
+      sink.lock().await.take();
+      env.get_undefined()
+    })
+  })?;
+
+  let mut underlying_sink = env.create_object()?;
+  underlying_sink.set_named_property("write", write)?;
+  underlying_sink.set_named_property("close", close)?;
+  underlying_sink.set_named_property("abort", abort)?;
+
+  web_stream_ctor(env, "WritableStream")?.new_instance(&[underlying_sink])
+}
@@ -0,0 +1,118 @@
+use std::cell::Cell;
+
+use futures::channel::oneshot;
+use futures::future::Either;
+use futures::Future;
+use napi::JsFunction;
+use napi::JsObject;
+use napi::JsUnknown;
+
+use crate::internal::call_method;
+use crate::JsException;
+use crate::JsRc;
+
+const SYM_ABORT_LISTENER: &str = "napi::abort_signal::listener";
+
+/// Runs a future on the local runtime until it completes or the JavaScript `AbortSignal` aborts.
+///
+/// When the signal aborts the future is dropped and the result is an error holding
+/// `signal.reason`, just like the rejections of `fetch()`. Without a signal the future runs
+/// to completion.
+///
+/// ```ignore
+/// #[napi]
+/// fn download(env: Env, url: String, signal: Option<JsRc<JsObject>>) -> napi::Result<JsObject> {
+///   env.spawn_local_promise(with_abort_signal(signal, async move {
+///     fetch(url).await
+///   }))
+/// }
+/// ```
+pub async fn with_abort_signal<T, Fut>(
+  signal: Option<JsRc<JsObject>>,
+  future: Fut,
+) -> napi::Result<T>
+where
+  Fut: Future<Output = napi::Result<T>>,
+{
+  let Some(signal) = signal else {
+    return future.await;
+  };
+
+  if signal
+    .get()?
+    .get_named_property::<JsUnknown>("aborted")?
+    .coerce_to_bool()?
+    .get_value()?
+  {
+    return Err(abort_reason(&signal)?);
+  }
+
+  let mut listener = AbortListener::add(signal.clone())?;
+
+  match futures::future::select(Box::pin(future), &mut listener.aborted).await {
+    Either::Left((result, _)) => result,
+    Either::Right(_) => Err(abort_reason(&signal)?),
+  }
+}
+
+fn abort_reason(signal: &JsRc<JsObject>) -> napi::Result<napi::Error> {
+  let reason = signal.get()?.get_named_property::<JsUnknown>("reason")?;
+  Ok(JsException::from_value(&signal.env(), reason)?.into())
+}
+
+/// An "abort" event listener that is removed when dropped
+struct AbortListener {
+  signal: JsRc<JsObject>,
+  listener: JsRc<JsFunction>,
+  aborted: oneshot::Receiver<()>,
+}
+
+impl AbortListener {
+  fn add(signal: JsRc<JsObject>) -> napi::Result<Self> {
+    let env = signal.env();
+    let (tx, rx) = oneshot::channel();
+    let tx = Cell::new(Some(tx));
+
+    let listener = env.create_function_from_closure(SYM_ABORT_LISTENER, move |ctx| {
+      if let Some(tx) = tx.take() {
+        tx.send(()).ok();
+      }
+      ctx.env.get_undefined()
+    })?;
+    let listener = JsRc::new(&env, listener)?;
+
+    call_method(
+      &signal.get()?,
+      "addEventListener",
+      &[
+        env.create_string("abort")?.into_unknown(),
+        listener.get()?.into_unknown(),
+      ],
+    )?;
+
+    Ok(Self {
+      signal,
+      listener,
+      aborted: rx,
+    })
+  }
+
+  fn remove(&self) -> napi::Result<()> {
+    let env = self.signal.env();
+    call_method(
+      &self.signal.get()?,
+      "removeEventListener",
+      &[
+        env.create_string("abort")?.into_unknown(),
+        self.listener.get()?.into_unknown(),
+      ],
+    )?;
+    Ok(())
+  }
+}
+
+impl Drop for AbortListener {
+  fn drop(&mut self) {
+    self.remove().ok();
+  }
+}
@@ -0,0 +1,30 @@
+use std::any::Any;
+use std::panic::AssertUnwindSafe;
+
+use futures::Future;
+use futures::FutureExt;
+
+/// Runs a future and converts a panic while polling it into an error, so the
+/// Promise it is spawned with rejects rather than the panic unwinding into the runtime.
+pub async fn catch_unwind<T, Fut>(future: Fut) -> napi::Result<T>
+where
+  Fut: Future<Output = napi::Result<T>>,
+{
+  match AssertUnwindSafe(future).catch_unwind().await {
+    Ok(result) => result,
+    Err(payload) => Err(napi::Error::from_reason(format!(
+      "panicked: {}",
+      panic_message(&payload)
+    ))),
+  }
+}
+
+fn panic_message(payload: &Box<dyn Any + Send>) -> &str {
+  if let Some(message) = payload.downcast_ref::<&str>() {
+    message
+  } else if let Some(message) = payload.downcast_ref::<String>() {
+    message
+  } else {
+    "Box<dyn Any>"
+  }
+}
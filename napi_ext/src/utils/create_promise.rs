@@ -1,24 +1,28 @@
 use std::cell::Cell;
+use std::rc::Rc;
 
+use napi::bindgen_prelude::ToNapiValue;
 use napi::Env;
 use napi::JsFunction;
 use napi::JsObject;
+use napi::JsUnknown;
 use napi::NapiValue;
 
+use crate::JsException;
 use crate::JsRc;
 
 const SYM_PROMISE: &str = "Promise";
 const SYM_PROMISE_EXECUTOR: &str = "napi::promise::executor";
 
 pub type PromiseExecutor<Res> =
-  Box<dyn FnOnce(Env, Box<dyn Fn(Res)>, Box<dyn Fn(napi::Error)>) -> napi::Result<()>>;
+  Box<dyn FnOnce(Env, Box<dyn Fn(Res)>, Box<dyn Fn(JsException)>) -> napi::Result<()>>;
 
 pub fn create_promise<Res>(
   env: &Env,
   executor: PromiseExecutor<Res>,
 ) -> napi::Result<JsObject>
 where
-  Res: NapiValue + 'static,
+  Res: ToNapiValue + 'static,
 {
   let promise_key = env.create_string(SYM_PROMISE)?;
   let promise_ctor: JsFunction = env.get_global()?.get_property_unchecked(promise_key)?;
@@ -39,20 +43,37 @@ where
     let resolve_func = JsRc::new(ctx.env, resolve_func_js)?;
     let reject_func = JsRc::new(ctx.env, reject_func)?;
 
+    let reject = Rc::new({
+      let env = *ctx.env;
+      move |e: JsException| {
+        let error = e.into_unknown(&env).unwrap();
+        reject_func
+          .borrow(&env)
+          .unwrap()
+          .call(None, &[error])
+          .unwrap();
+      }
+    });
+
     executor(
       ctx.env.to_owned(),
-      Box::new({
-        move |r| {
-          resolve_func.call(None, &[r]).unwrap();
-        }
-      }),
       Box::new({
         let env = *ctx.env;
-        move |e| {
-          let error = (env).create_error(e).unwrap();
-          reject_func.call(None, &[error]).unwrap();
+        let reject = reject.clone();
+        move |r| match unsafe { Res::to_napi_value(env.raw(), r) } {
+          Ok(value) => {
+            let value = unsafe { JsUnknown::from_raw_unchecked(env.raw(), value) };
+            resolve_func
+              .borrow(&env)
+              .unwrap()
+              .call(None, &[value])
+              .unwrap();
+          }
+          // Values that fail to convert reject the Promise
+          Err(error) => reject(error.into()),
         }
       }),
+      Box::new(move |e| reject(e)),
     )?;
 
     Ok(())
@@ -0,0 +1,115 @@
+use std::future::Future;
+use std::marker::PhantomData;
+
+use napi::bindgen_prelude::FromNapiValue;
+use napi::bindgen_prelude::JsValuesTupleIntoVec;
+use napi::bindgen_prelude::TypeName;
+use napi::sys as napi_sys;
+use napi::JsFunction;
+use napi::JsUnknown;
+use napi::NapiRaw;
+use napi::NapiValue;
+use napi::ValueType;
+
+use crate::JsRc;
+
+/// A JavaScript function with typed arguments and return value.
+///
+/// `Args` is a tuple of [`napi::bindgen_prelude::ToNapiValue`] values and `Ret` is converted
+/// from the value the function returns. The function is held in a [`JsRc`] so it can be
+/// kept across awaits. The function may return a Promise, which only [`JsCallback::call_async`] awaits.
+///
+/// The TypeScript type `(arg0: A, arg1: B) => R | Promise<R>` is only written by `#[napi_async]`
+/// and `#[napi_stream]`. napi-derive does not know this type, so parameters of plain `#[napi]`
+/// functions need `#[napi(ts_arg_type = "...")]`.
+///
+/// ```ignore
+/// #[napi_async]
+/// async fn transform(
+///   paths: Vec<String>,
+///   transformer: JsCallback<(String, u32), String>,
+/// ) -> napi::Result<Vec<String>> {
+///   let mut results = vec![];
+///   for (i, path) in paths.into_iter().enumerate() {
+///     results.push(transformer.call_async((path, i as u32)).await?);
+///   }
+///   Ok(results)
+/// }
+/// ```
+pub struct JsCallback<Args, Ret = JsUnknown> {
+  callback: JsRc<JsFunction>,
+  _signature: PhantomData<fn(Args) -> Ret>,
+}
+
+impl<Args, Ret> JsCallback<Args, Ret>
+where
+  Args: JsValuesTupleIntoVec,
+  Ret: FromNapiValue,
+{
+  pub fn new(callback: JsRc<JsFunction>) -> Self {
+    Self {
+      callback,
+      _signature: PhantomData,
+    }
+  }
+
+  /// Calls the function and converts the value it returns
+  pub fn call(
+    &self,
+    args: Args,
+  ) -> napi::Result<Ret> {
+    let env = self.callback.env();
+    let args = args
+      .into_vec(env.raw())?
+      .into_iter()
+      .map(|arg| unsafe { JsUnknown::from_raw_unchecked(env.raw(), arg) })
+      .collect::<Vec<_>>();
+
+    let value = self.callback.get()?.call(None, &args)?;
+    unsafe { Ret::from_napi_value(env.raw(), value.raw()) }
+  }
+
+  /// Calls the function and awaits its result on the local runtime if it returns a thenable,
+  /// such as the Promise returned by an `async` function
+  pub fn call_async(
+    &self,
+    args: Args,
+  ) -> impl Future<Output = napi::Result<Ret>> {
+    self.callback.call_async(None, args)
+  }
+
+  pub fn function(&self) -> &JsRc<JsFunction> {
+    &self.callback
+  }
+}
+
+impl<Args, Ret> Clone for JsCallback<Args, Ret> {
+  fn clone(&self) -> Self {
+    Self {
+      callback: self.callback.clone(),
+      _signature: PhantomData,
+    }
+  }
+}
+
+impl<Args, Ret> FromNapiValue for JsCallback<Args, Ret> {
+  unsafe fn from_napi_value(
+    env: napi_sys::napi_env,
+    napi_val: napi_sys::napi_value,
+  ) -> napi::Result<Self> {
+    Ok(Self {
+      callback: JsRc::from_napi_value(env, napi_val)?,
+      _signature: PhantomData,
+    })
+  }
+}
+
+impl<Args, Ret> TypeName for JsCallback<Args, Ret> {
+  fn type_name() -> &'static str {
+    "Function"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Function
+  }
+}
@@ -0,0 +1,108 @@
+use std::future::Future;
+use std::marker::PhantomData;
+
+use futures::channel::mpsc;
+use futures::channel::oneshot;
+use futures::StreamExt;
+use napi::bindgen_prelude::FromNapiValue;
+use napi::bindgen_prelude::JsValuesTupleIntoVec;
+use napi::JsFunction;
+
+use crate::JsRc;
+use crate::SpawnLocalExt;
+
+struct Call<Args, Ret> {
+  args: Args,
+  tx: oneshot::Sender<napi::Result<Ret>>,
+}
+
+/// A [`Send`] handle to a JavaScript function that can be called from any thread.
+///
+/// Calls are sent to a task on the local runtime which invokes the function on the
+/// JavaScript thread, awaits the result if it is a thenable and sends the converted value
+/// back. The task (and the Nodejs process) stays alive until every clone of the handle
+/// is dropped.
+///
+/// ```ignore
+/// #[napi]
+/// fn resolve(env: Env, plugin: JsRc<JsFunction>) -> napi::Result<JsObject> {
+///   let plugin = JsCallbackHandle::<(String,), bool>::new(plugin)?;
+///   env.spawn_thread(move || {
+///     let accepted = plugin.call_blocking(("./index.js".to_string(),))?;
+///     Ok(move |env| Ok(accepted))
+///   })
+/// }
+/// ```
+pub struct JsCallbackHandle<Args, Ret> {
+  tx: mpsc::UnboundedSender<Call<Args, Ret>>,
+  _ret: PhantomData<fn() -> Ret>,
+}
+
+impl<Args, Ret> JsCallbackHandle<Args, Ret>
+where
+  Args: JsValuesTupleIntoVec + Send + 'static,
+  Ret: FromNapiValue + Send + 'static,
+{
+  pub fn new(callback: JsRc<JsFunction>) -> napi::Result<Self> {
+    let env = callback.env();
+    let (tx, rx) = mpsc::unbounded::<Call<Args, Ret>>();
+
+    env.spawn_local(async move {
+      rx.for_each_concurrent(None, |call| {
+        let callback = callback.clone();
+        async move {
+          let result = callback.call_async(None, call.args).await;
+          call.tx.send(result).ok();
+        }
+      })
+      .await;
+      Ok(())
+    })?;
+
+    Ok(Self {
+      tx,
+      _ret: PhantomData,
+    })
+  }
+
+  /// Calls the function on the JavaScript thread and resolves to its return value
+  pub fn call(
+    &self,
+    args: Args,
+  ) -> impl Future<Output = napi::Result<Ret>> + Send + 'static {
+    let (tx, rx) = oneshot::channel();
+    let sent = self.tx.unbounded_send(Call { args, tx }).is_ok();
+
+    async move {
+      if !sent {
+        return Err(napi::Error::from_reason(
+          "JsCallbackHandle is no longer running",
+        ));
+      }
+      match rx.await {
+        Ok(result) => result,
+        Err(_) => Err(napi::Error::from_reason(
+          "JsCallbackHandle was dropped before the call completed",
+        )),
+      }
+    }
+  }
+
+  /// Calls the function and blocks the current thread until it returns.
+  /// This will deadlock if called from the JavaScript thread.
+  pub fn call_blocking(
+    &self,
+    args: Args,
+  ) -> napi::Result<Ret> {
+    futures::executor::block_on(self.call(args))
+  }
+}
+
+impl<Args, Ret> Clone for JsCallbackHandle<Args, Ret> {
+  fn clone(&self) -> Self {
+    Self {
+      tx: self.tx.clone(),
+      _ret: PhantomData,
+    }
+  }
+}
@@ -1,11 +1,20 @@
 use std::cell::Cell;
+use std::future::Future;
+use std::rc::Rc;
 
+use futures::channel::oneshot;
 use napi::Env;
 use napi::JsFunction;
 use napi::JsObject;
+use napi::JsUnknown;
 use napi::NapiValue;
 
+use crate::JsException;
+use crate::JsRc;
+
 const SYM_JS_PROMISE_THEN: &str = "napi::promise::then";
+const SYM_JS_PROMISE_FULFILLED: &str = "napi::promise::fulfilled";
+const SYM_JS_PROMISE_REJECTED: &str = "napi::promise::rejected";
 
 pub struct JsPromise<'a> {
   env: &'a Env,
@@ -85,4 +94,50 @@ impl<'a> JsPromise<'a> {
 
     Ok(self)
   }
+
+  /// Converts the Promise into a Rust [`Future`] that can be awaited on the local runtime.
+  /// A rejection resolves to a [`napi::Error`] holding the original rejection value.
+  pub fn into_future(self) -> napi::Result<impl Future<Output = napi::Result<JsRc<JsUnknown>>>> {
+    let (tx, rx) = oneshot::channel::<napi::Result<JsRc<JsUnknown>>>();
+    let tx = Rc::new(Cell::new(Some(tx)));
+
+    let on_fulfilled = self
+      .env
+      .create_function_from_closure(SYM_JS_PROMISE_FULFILLED, {
+        let tx = tx.clone();
+        move |ctx| {
+          let value = ctx.get::<JsUnknown>(0)?;
+          if let Some(tx) = tx.take() {
+            tx.send(JsRc::new(ctx.env, value)).ok();
+          }
+          ctx.env.get_undefined()
+        }
+      })?;
+
+    let on_rejected =
+      self
+        .env
+        .create_function_from_closure(SYM_JS_PROMISE_REJECTED, move |ctx| {
+          let reason = ctx.get::<JsUnknown>(0)?;
+          if let Some(tx) = tx.take() {
+            let reason = JsException::from_value(ctx.env, reason)?;
+            tx.send(Err(reason.into())).ok();
+          }
+          ctx.env.get_undefined()
+        })?;
+
+    self
+      .inner
+      .get_named_property::<JsFunction>("then")?
+      .call(Some(&self.inner), &[on_fulfilled, on_rejected])?;
+
+    Ok(async move {
+      match rx.await {
+        Ok(result) => result,
+        Err(_) => Err(napi::Error::from_reason(
+          "Promise was dropped before settling",
+        )),
+      }
+    })
+  }
 }
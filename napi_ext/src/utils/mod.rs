@@ -1,11 +1,29 @@
+mod abort_signal;
+mod catch_unwind;
 mod console_log;
 mod create_promise;
+mod js_callback;
+mod js_callback_handle;
 mod js_promise;
+#[cfg(feature = "serde")]
+mod serde_value;
+mod serialize;
 mod spawn_thread;
+mod thread_arg;
+mod timeout;
 mod utils_ext;
 
+pub use self::abort_signal::*;
+pub use self::catch_unwind::*;
 pub use self::console_log::*;
 pub use self::create_promise::*;
+pub use self::js_callback::*;
+pub use self::js_callback_handle::*;
 pub use self::js_promise::*;
+#[cfg(feature = "serde")]
+pub use self::serde_value::*;
+pub use self::serialize::*;
 pub use self::spawn_thread::*;
+pub use self::thread_arg::*;
+pub use self::timeout::*;
 pub use self::utils_ext::*;
@@ -0,0 +1,53 @@
+use napi::bindgen_prelude::FromNapiValue;
+use napi::bindgen_prelude::ToNapiValue;
+use napi::bindgen_prelude::TypeName;
+use napi::sys as napi_sys;
+use napi::Env;
+use napi::JsUnknown;
+use napi::NapiRaw;
+use napi::NapiValue;
+use napi::ValueType;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Converts a Rust value to and from JavaScript using napi's serde support.
+///
+/// Wrap a [`Serialize`] value to return it from [`crate::SpawnLocalExt::spawn_local_promise`]
+/// or a `#[napi_async]` function, or use it as a parameter to deserialize a JavaScript value.
+pub struct SerdeValue<T>(pub T);
+
+impl<T> SerdeValue<T> {
+  pub fn into_inner(self) -> T {
+    self.0
+  }
+}
+
+impl<T: Serialize> ToNapiValue for SerdeValue<T> {
+  unsafe fn to_napi_value(
+    env: napi_sys::napi_env,
+    val: Self,
+  ) -> napi::Result<napi_sys::napi_value> {
+    let value = Env::from_raw(env).to_js_value(&val.0)?;
+    Ok(value.raw())
+  }
+}
+
+impl<T: DeserializeOwned> FromNapiValue for SerdeValue<T> {
+  unsafe fn from_napi_value(
+    env: napi_sys::napi_env,
+    napi_val: napi_sys::napi_value,
+  ) -> napi::Result<Self> {
+    let value = JsUnknown::from_raw_unchecked(env, napi_val);
+    Ok(Self(Env::from_raw(env).from_js_value(value)?))
+  }
+}
+
+impl<T> TypeName for SerdeValue<T> {
+  fn type_name() -> &'static str {
+    "any"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Unknown
+  }
+}
@@ -0,0 +1,107 @@
+use napi::bindgen_prelude::FromNapiValue;
+use napi::bindgen_prelude::ToNapiValue;
+use napi::bindgen_prelude::TypeName;
+use napi::sys as napi_sys;
+use napi::Env;
+use napi::JsBuffer;
+use napi::JsUnknown;
+use napi::NapiRaw;
+use napi::NapiValue;
+use napi::ValueType;
+
+use crate::internal::call_method;
+use crate::internal::require_builtin;
+
+/// Serializes a JavaScript value to bytes with Node's `v8.serialize()`.
+///
+/// Any value supported by the structured clone algorithm can be serialized, including
+/// typed arrays, `Map`s, `Set`s, `Date`s, `RegExp`s, errors and values containing cycles.
+/// Class instances are serialized as plain objects.
+pub fn serialize<V: NapiRaw>(
+  env: &Env,
+  value: V,
+) -> napi::Result<Vec<u8>> {
+  let value = unsafe { JsUnknown::from_raw_unchecked(env.raw(), value.raw()) };
+  let buffer = call_method(&require_builtin(env, "v8")?, "serialize", &[value])?;
+  let buffer = JsBuffer::try_from(buffer)?;
+  Ok(buffer.into_value()?.to_vec())
+}
+
+/// Recreates a JavaScript value from bytes produced by [`serialize`]
+/// with Node's `v8.deserialize()`
+pub fn deserialize(
+  env: &Env,
+  bytes: &[u8],
+) -> napi::Result<JsUnknown> {
+  let buffer = env.create_buffer_with_data(bytes.to_vec())?.into_raw();
+  call_method(
+    &require_builtin(env, "v8")?,
+    "deserialize",
+    &[buffer.into_unknown()],
+  )
+}
+
+/// A JavaScript value serialized with [`serialize`] that can be sent to other threads.
+///
+/// Use it as a parameter to serialize a JavaScript value, or return it from
+/// [`crate::UtilsExt::spawn_thread`] to deserialize it back into JavaScript.
+///
+/// The napi_ext macros type it as `any`, plain `#[napi]` functions need `ts_arg_type`.
+///
+/// ```ignore
+/// #[napi]
+/// fn echo(env: Env, #[napi(ts_arg_type = "any")] value: Serialized) -> napi::Result<JsObject> {
+///   env.spawn_thread(move || Ok(move |_env| Ok(value)))
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Serialized(pub Vec<u8>);
+
+impl Serialized {
+  pub fn new<V: NapiRaw>(
+    env: &Env,
+    value: V,
+  ) -> napi::Result<Self> {
+    Ok(Self(serialize(env, value)?))
+  }
+
+  pub fn to_js(
+    &self,
+    env: &Env,
+  ) -> napi::Result<JsUnknown> {
+    deserialize(env, &self.0)
+  }
+
+  pub fn into_inner(self) -> Vec<u8> {
+    self.0
+  }
+}
+
+impl ToNapiValue for Serialized {
+  unsafe fn to_napi_value(
+    env: napi_sys::napi_env,
+    val: Self,
+  ) -> napi::Result<napi_sys::napi_value> {
+    Ok(val.to_js(&Env::from_raw(env))?.raw())
+  }
+}
+
+impl FromNapiValue for Serialized {
+  unsafe fn from_napi_value(
+    env: napi_sys::napi_env,
+    napi_val: napi_sys::napi_value,
+  ) -> napi::Result<Self> {
+    let value = JsUnknown::from_raw_unchecked(env, napi_val);
+    Serialized::new(&Env::from_raw(env), value)
+  }
+}
+
+impl TypeName for Serialized {
+  fn type_name() -> &'static str {
+    "any"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Unknown
+  }
+}
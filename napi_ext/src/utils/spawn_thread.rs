@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::thread;
 
 use napi::bindgen_prelude::FromNapiValue;
@@ -11,12 +12,14 @@ use napi::Env;
 use napi::JsFunction;
 use napi::JsObject;
 use napi::JsUnknown;
-use once_cell::sync::OnceCell;
+
+use crate::internal::call_method;
+use crate::JsException;
 
 type MapJsParams = Box<dyn Send + FnOnce(&Env) -> napi::Result<Vec<JsUnknown>> + 'static>;
 
 /// Creates a system thread and returns a Promise back to JavaScript.
-/// Captures errors and returns them as a JavaScript tuple
+/// Errors returned by either function reject the Promise
 pub fn spawn_thread<ThreadFunc, NapiFunc, NapiRet>(
   env: &Env,
   func: ThreadFunc,
@@ -29,7 +32,7 @@ where
   // Captures the executor function of Promise creation as a threadsafe function
   //   new Promise(resolve => {})
   //               -------  <- this bit
-  let resolve_fn = Arc::new(OnceCell::new());
+  let resolve_fn = Arc::new(Mutex::new(None));
 
   // This is the callback supplied to `new Promise(executor)`
   let executor = env.create_function_from_closure("Promise::executor", {
@@ -38,9 +41,12 @@ where
       let resolve: ThreadsafeFunction<MapJsParams, ErrorStrategy::Fatal> = ctx
         .get::<JsFunction>(0)?
         .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<MapJsParams>| {
-          (ctx.value)(&ctx.env)
+          match (ctx.value)(&ctx.env) {
+            Ok(values) => Ok(values),
+            Err(error) => Ok(vec![rejected_promise(&ctx.env, error)?]),
+          }
         })?;
-      resolve_fn.set(resolve).ok();
+      resolve_fn.lock().unwrap().replace(resolve);
       Ok(())
     }
   })?;
@@ -55,8 +61,15 @@ where
     // Call the function on the new thread
     let result = func();
 
+    // The executor runs synchronously within the Promise constructor so this is set.
+    // It is taken so the threadsafe function is released after the call, allowing
+    // Nodejs to exit
+    let Some(resolve) = resolve_fn.lock().unwrap().take() else {
+      return;
+    };
+
     // Process the return value on the JS thread
-    resolve_fn.wait().call(
+    resolve.call(
       Box::new(move |env| match result {
         Ok(value) => {
           // Execute the function passed in by the caller
@@ -72,7 +85,6 @@ where
 
           Ok(vec![js_value])
         }
-        // Capture errors and and return them as string values
         Err(error) => Err(error),
       }),
       ThreadsafeFunctionCallMode::NonBlocking,
@@ -81,3 +93,16 @@ where
 
   Ok(promise)
 }
+
+/// Resolving a Promise with a rejected Promise rejects it with the same reason
+fn rejected_promise(
+  env: &Env,
+  error: napi::Error,
+) -> napi::Result<JsUnknown> {
+  let reason = JsException::from(error).into_unknown(env)?;
+  let promise_ctor = env
+    .get_global()?
+    .get_named_property_unchecked::<JsObject>("Promise")?;
+
+  call_method(&promise_ctor, "reject", &[reason])
+}
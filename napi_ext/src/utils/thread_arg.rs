@@ -0,0 +1,14 @@
+/// Implemented for the parameter types of a `#[napi_thread]` function, which are
+/// moved to the thread running it
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+  message = "`{Self}` cannot be sent to the thread of a #[napi_thread] function",
+  label = "this parameter is not Send",
+  note = "use an owned Send type, or JsValueOwned or Serialized for JavaScript values"
+)]
+pub trait ThreadArg {}
+
+impl<T: Send> ThreadArg for T {}
+
+#[doc(hidden)]
+pub fn assert_thread_arg<T: ThreadArg>() {}
@@ -0,0 +1,124 @@
+use std::cell::Cell;
+use std::time::Duration;
+
+use futures::channel::oneshot;
+use futures::future::Either;
+use futures::Future;
+use napi::Env;
+use napi::JsFunction;
+use napi::JsUnknown;
+use napi::ValueType;
+
+use crate::internal::call_method;
+use crate::JsException;
+use crate::JsRc;
+
+const SYM_TIMEOUT_CALLBACK: &str = "napi::timeout::callback";
+
+/// The longest delay setTimeout supports, longer delays fire at once
+const MAX_TIMEOUT_MS: u128 = i32::MAX as u128;
+
+/// Runs a future on the local runtime until it completes or the duration elapses.
+///
+/// When the duration elapses first the future is dropped and the result is a JavaScript
+/// `Error` named `TimeoutError`. The timer is a JavaScript `setTimeout` that is unref'd
+/// and cleared when the future completes, so it does not keep Nodejs alive on its own.
+/// Durations longer than `setTimeout` supports, about 24.8 days, are an error.
+///
+/// ```ignore
+/// env.spawn_local_promise(with_timeout(env, Duration::from_secs(5), async move {
+///   fetch_data().await
+/// }))
+/// ```
+pub async fn with_timeout<T, Fut>(
+  env: Env,
+  duration: Duration,
+  future: Fut,
+) -> napi::Result<T>
+where
+  Fut: Future<Output = napi::Result<T>>,
+{
+  if duration.as_millis() > MAX_TIMEOUT_MS {
+    return Err(napi::Error::from_reason(format!(
+      "Timeouts cannot be longer than {}ms",
+      MAX_TIMEOUT_MS
+    )));
+  }
+
+  let mut timer = Timer::start(&env, duration)?;
+
+  match futures::future::select(Box::pin(future), &mut timer.elapsed).await {
+    Either::Left((result, _)) => result,
+    Either::Right(_) => {
+      let mut error = env.create_error(napi::Error::from_reason(format!(
+        "Timed out after {:?}",
+        duration
+      )))?;
+      error.set_named_property("name", env.create_string("TimeoutError")?)?;
+      Err(JsException::from_value(&env, error)?.into())
+    }
+  }
+}
+
+/// A JavaScript timer that is cleared when dropped
+struct Timer {
+  handle: JsRc<JsUnknown>,
+  elapsed: oneshot::Receiver<()>,
+}
+
+impl Timer {
+  fn start(
+    env: &Env,
+    duration: Duration,
+  ) -> napi::Result<Self> {
+    let (tx, rx) = oneshot::channel();
+    let tx = Cell::new(Some(tx));
+
+    let callback = env.create_function_from_closure(SYM_TIMEOUT_CALLBACK, move |ctx| {
+      if let Some(tx) = tx.take() {
+        tx.send(()).ok();
+      }
+      ctx.env.get_undefined()
+    })?;
+
+    let mut handle = env
+      .get_global()?
+      .get_named_property::<JsFunction>("setTimeout")?
+      .call(
+        None,
+        &[
+          callback.into_unknown(),
+          env
+            .create_double(duration.as_secs_f64() * 1000.0)?
+            .into_unknown(),
+        ],
+      )?;
+
+    // Node.js returns a Timeout object, the pending future keeps the process alive instead
+    if handle.get_type()? == ValueType::Object {
+      let timeout = handle.coerce_to_object()?;
+      call_method(&timeout, "unref", &[])?;
+      handle = timeout.into_unknown();
+    }
+
+    Ok(Self {
+      handle: JsRc::new(env, handle)?,
+      elapsed: rx,
+    })
+  }
+}
+
+impl Drop for Timer {
+  fn drop(&mut self) {
+    let env = self.handle.env();
+    let Ok(handle) = self.handle.get() else {
+      return;
+    };
+    let clear_timeout = env
+      .get_global()
+      .and_then(|global| global.get_named_property::<JsFunction>("clearTimeout"));
+    if let Ok(clear_timeout) = clear_timeout {
+      clear_timeout.call(None, &[handle]).ok();
+    }
+  }
+}
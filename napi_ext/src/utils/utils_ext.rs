@@ -1,7 +1,7 @@
 use napi::bindgen_prelude::ToNapiValue;
 use napi::Env;
 use napi::JsObject;
-use napi::NapiValue;
+use napi::NapiRaw;
 
 use super::console_log;
 use super::create_promise;
@@ -10,8 +10,8 @@ use super::PromiseExecutor;
 
 pub trait UtilsExt {
   /// Runs console.log() in the JavaScript context.
-  /// useful for debugging [`NapiValue`] types
-  fn console_log<V: NapiValue>(
+  /// useful for debugging [`NapiRaw`] types
+  fn console_log<V: NapiRaw>(
     &self,
     args: &[V],
   ) -> napi::Result<()>;
@@ -21,7 +21,7 @@ pub trait UtilsExt {
     executor: PromiseExecutor<Res>,
   ) -> napi::Result<JsObject>
   where
-    Res: NapiValue + 'static;
+    Res: ToNapiValue + 'static;
 
   fn spawn_thread<ThreadFunc, NapiFunc, NapiRet>(
     &self,
@@ -34,7 +34,7 @@ pub trait UtilsExt {
 }
 
 impl UtilsExt for Env {
-  fn console_log<V: NapiValue>(
+  fn console_log<V: NapiRaw>(
     &self,
     args: &[V],
   ) -> napi::Result<()> {
@@ -46,7 +46,7 @@ impl UtilsExt for Env {
     executor: PromiseExecutor<Res>,
   ) -> napi::Result<JsObject>
   where
-    Res: NapiValue + 'static,
+    Res: ToNapiValue + 'static,
   {
     create_promise(self, executor)
   }